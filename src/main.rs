@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
-use image::io::Reader as ImageReader;
-use rusty_tesseract::{Args, Image};
-/// Refer to https://github.com/thomasgruebl/rusty-tesseract
+use image::ImageReader;
+use rusty_tesseract::{Args, Image, OcrEngineMode, PageSegMode};
+// Refer to https://github.com/thomasgruebl/rusty-tesseract
 
 // the main function provides usage samples of the rusty-tesseract wrapper
 fn main() {
@@ -26,7 +26,10 @@ fn main() {
     let default_args = Args::default();
 
     let tesseract_version = rusty_tesseract::get_tesseract_version().unwrap();
-    println!("The tesseract version is: {}", tesseract_version);
+    println!(
+        "The tesseract version is: {}.{}.{}",
+        tesseract_version.major, tesseract_version.minor, tesseract_version.patch
+    );
 
     let tesseract_langs = rusty_tesseract::get_tesseract_langs().unwrap();
     println!("The available languages are: {:?}", tesseract_langs);
@@ -39,8 +42,9 @@ fn main() {
             "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".into(),
         )]),
         dpi: Some(150),
-        psm: Some(6),
-        oem: Some(3),
+        psm: Some(PageSegMode::SingleBlock),
+        oem: Some(OcrEngineMode::Default),
+        ..Default::default()
     };
 
     //you can get the list of available config variables with:
@@ -80,7 +84,7 @@ mod tests {
         let img = Image::from_path("img/vertical_text.png").unwrap();
 
         let image_to_string_args = Args {
-            psm: Some(6),
+            psm: Some(PageSegMode::SingleBlock),
             ..Default::default()
         };
 
@@ -1,16 +1,52 @@
+pub mod args_builder;
+#[cfg(feature = "async")]
+pub mod asynchronous;
+pub mod batch;
 pub mod command;
+pub mod deskew;
+pub mod document;
 pub mod error;
 pub mod input;
+pub mod languages;
+pub mod modes;
+pub mod output_alto;
 pub mod output_boxes;
+pub mod output_combined;
 pub mod output_config_parameters;
 pub mod output_data;
+pub mod output_hocr;
+pub mod output_orientation;
+pub mod output_pdf;
+pub mod output_version;
+pub mod pages;
+pub mod preprocess;
+pub mod rect;
+pub mod regions;
 
+pub use args_builder::*;
+#[cfg(feature = "async")]
+pub use asynchronous::*;
+pub use batch::*;
 pub use command::*;
+pub use deskew::*;
+pub use document::*;
 pub use error::*;
 pub use input::*;
+pub use languages::*;
+pub use modes::*;
+pub use output_alto::*;
 pub use output_boxes::*;
+pub use output_combined::*;
 pub use output_config_parameters::*;
 pub use output_data::*;
+pub use output_hocr::*;
+pub use output_orientation::*;
+pub use output_pdf::*;
+pub use output_version::*;
+pub use pages::*;
+pub use preprocess::*;
+pub use rect::*;
+pub use regions::*;
 
 mod parse_line_util;
 use parse_line_util::*;
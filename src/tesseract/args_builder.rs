@@ -0,0 +1,191 @@
+use super::*;
+use core::fmt;
+use std::path::PathBuf;
+
+/// Fluent builder for `Args`.
+///
+/// Optionally validates the language and the config variable names against the
+/// installed tesseract before any OCR is run.
+#[derive(Clone, Debug, Default)]
+pub struct ArgsBuilder {
+    args: Args,
+    validation: Option<TesseractEngine>,
+}
+
+impl Args {
+    pub fn builder() -> ArgsBuilder {
+        ArgsBuilder::default()
+    }
+
+    /// Checks `lang` against `get_tesseract_langs_for()` and every config variable name
+    /// against `get_tesseract_config_parameters()` of the given engine.
+    pub fn validate(&self, engine: &TesseractEngine) -> TessResult<()> {
+        let mut invalid = Vec::new();
+
+        let langs = engine.get_tesseract_langs_for(self)?;
+        invalid.extend(
+            self.lang
+                .unavailable(&langs)
+                .map(|lang| InvalidArgument::UnknownLanguage(lang.to_string())),
+        );
+
+        if !self.config_variables.is_empty() {
+            let parameters = engine.get_tesseract_config_parameters()?;
+            let mut names = self
+                .config_variables
+                .keys()
+                .filter(|name| {
+                    !parameters
+                        .config_parameters
+                        .iter()
+                        .any(|x| x.name == **name)
+                })
+                .collect::<Vec<_>>();
+            names.sort();
+            invalid.extend(
+                names
+                    .into_iter()
+                    .map(|name| InvalidArgument::UnknownConfigVariable(name.clone())),
+            );
+        }
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(TessError::InvalidArgsError(invalid))
+        }
+    }
+}
+
+impl ArgsBuilder {
+    pub fn lang<L: Into<Languages>>(mut self, lang: L) -> Self {
+        self.args.lang = lang.into();
+        self
+    }
+
+    pub fn config_variable<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.args.config_variables.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn dpi(mut self, dpi: Option<i32>) -> Self {
+        self.args.dpi = dpi;
+        self
+    }
+
+    pub fn psm(mut self, psm: Option<PageSegMode>) -> Self {
+        self.args.psm = psm;
+        self
+    }
+
+    pub fn oem(mut self, oem: Option<OcrEngineMode>) -> Self {
+        self.args.oem = oem;
+        self
+    }
+
+    pub fn user_words<W: Into<WordList>>(mut self, user_words: W) -> Self {
+        self.args.user_words = Some(user_words.into());
+        self
+    }
+
+    pub fn user_patterns<W: Into<WordList>>(mut self, user_patterns: W) -> Self {
+        self.args.user_patterns = Some(user_patterns.into());
+        self
+    }
+
+    pub fn config_file<P: Into<PathBuf>>(mut self, config_file: P) -> Self {
+        self.args.config_files.push(config_file.into());
+        self
+    }
+
+    pub fn tessdata_dir<P: Into<PathBuf>>(mut self, tessdata_dir: P) -> Self {
+        self.args.tessdata_dir = Some(tessdata_dir.into());
+        self
+    }
+
+    pub fn auto_rotate(mut self, auto_rotate: bool) -> Self {
+        self.args.auto_rotate = auto_rotate;
+        self
+    }
+
+    /// Validate the arguments against the tesseract found on the `PATH` in `build()`.
+    pub fn validate(self) -> Self {
+        self.validate_with(TesseractEngine::default())
+    }
+
+    /// Validate the arguments against the given tesseract engine in `build()`.
+    pub fn validate_with(mut self, engine: TesseractEngine) -> Self {
+        self.validation = Some(engine);
+        self
+    }
+
+    pub fn build(self) -> TessResult<Args> {
+        if let Some(engine) = &self.validation {
+            self.args.validate(engine)?;
+        }
+
+        Ok(self.args)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InvalidArgument {
+    UnknownLanguage(String),
+    UnknownConfigVariable(String),
+}
+
+impl fmt::Display for InvalidArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidArgument::UnknownLanguage(lang) => write!(f, "unknown language '{}'", lang),
+            InvalidArgument::UnknownConfigVariable(name) => {
+                write!(f, "unknown config variable '{}'", name)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_builder() {
+        let args = Args::builder()
+            .lang("deu")
+            .config_variable("tessedit_char_whitelist", "0123456789")
+            .dpi(None)
+            .psm(Some(PageSegMode::SingleLine))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            args,
+            Args {
+                lang: "deu".into(),
+                config_variables: [("tessedit_char_whitelist".into(), "0123456789".into())].into(),
+                dpi: None,
+                psm: Some(PageSegMode::SingleLine),
+                ..Args::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        let result = Args::builder()
+            .lang("eng+xyz")
+            .config_variable("tessedit_char_whitelist", "0123456789")
+            .config_variable("no_such_variable", "1")
+            .validate()
+            .build();
+
+        assert_eq!(
+            result,
+            Err(TessError::InvalidArgsError(vec![
+                InvalidArgument::UnknownLanguage("xyz".into()),
+                InvalidArgument::UnknownConfigVariable("no_such_variable".into()),
+            ]))
+        );
+    }
+}
@@ -0,0 +1,969 @@
+use super::*;
+use std::ffi::OsStr;
+use std::future::{self, Future};
+use std::ops::RangeBounds;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::Poll;
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::error::{TessError, TessResult};
+
+impl TesseractEngine {
+    pub async fn get_tesseract_version_async(&self) -> TessResult<TesseractVersion> {
+        let mut command = self.command();
+        command.arg("--version");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        string_to_version(output)
+    }
+
+    /// Returns the installed version if it is at least `major.minor.patch`.
+    pub async fn require_tesseract_version_async(
+        &self,
+        major: u32,
+        minor: u32,
+        patch: u32,
+    ) -> TessResult<TesseractVersion> {
+        let version = self.get_tesseract_version_async().await?;
+        version.require(major, minor, patch)?;
+        Ok(version)
+    }
+
+    pub async fn get_tesseract_langs_async(&self) -> TessResult<Vec<String>> {
+        let mut command = self.command();
+        command.arg("--list-langs");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        Ok(parse_langs(&output))
+    }
+
+    pub async fn get_tesseract_langs_for_async(&self, args: &Args) -> TessResult<Vec<String>> {
+        let mut command = self.command_in(args.tessdata_dir.as_deref());
+        command.arg("--list-langs");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        Ok(parse_langs(&output))
+    }
+
+    pub async fn get_tesseract_config_parameters_async(&self) -> TessResult<ConfigParameterOutput> {
+        let mut command = self.command();
+        command.arg("--print-parameters");
+
+        let output = self.run_tesseract_command_async(command).await?;
+
+        let config_parameters = string_to_config_parameter_output(&output)?;
+
+        Ok(ConfigParameterOutput {
+            output,
+            config_parameters,
+        })
+    }
+
+    pub async fn image_to_string_async(&self, image: &Image, args: &Args) -> TessResult<String> {
+        let command = self.create_tesseract_command_async(image, args).await?;
+        self.run_tesseract_command_async(command).await
+    }
+
+    pub async fn image_to_boxes_async(&self, image: &Image, args: &Args) -> TessResult<BoxOutput> {
+        let mut command = self.create_tesseract_command_async(image, args).await?;
+        command.arg("makebox");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        let boxes = string_to_boxes(&output)?;
+        Ok(BoxOutput { output, boxes })
+    }
+
+    pub async fn image_to_data_async(&self, image: &Image, args: &Args) -> TessResult<DataOutput> {
+        let mut command = self.create_tesseract_command_async(image, args).await?;
+        command.arg("tsv");
+
+        let output = self.run_tesseract_command_async(command).await?;
+
+        let data = string_to_data(&output)?;
+
+        Ok(DataOutput { output, data })
+    }
+
+    pub async fn image_to_hocr_async(&self, image: &Image, args: &Args) -> TessResult<HocrOutput> {
+        let mut command = self.create_tesseract_command_async(image, args).await?;
+        command.arg("hocr");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        let pages = string_to_hocr(&output)?;
+
+        Ok(HocrOutput { output, pages })
+    }
+
+    pub async fn image_to_alto_async(&self, image: &Image, args: &Args) -> TessResult<AltoOutput> {
+        let mut command = self.create_tesseract_command_async(image, args).await?;
+        command.arg("alto");
+
+        let output = self.run_tesseract_command_async(command).await?;
+        let alto = string_to_alto(&output)?;
+
+        Ok(AltoOutput { output, alto })
+    }
+
+    pub async fn image_to_pdf_async(
+        &self,
+        image: &Image,
+        args: &Args,
+        mode: PdfMode,
+    ) -> TessResult<PdfOutput> {
+        let mut command = self
+            .create_tesseract_command_async(image, &pdf_args(args, mode))
+            .await?;
+        command.arg("pdf");
+
+        let output = self.run_tesseract_command_bytes_async(command).await?;
+        Ok(PdfOutput { output })
+    }
+
+    pub async fn images_to_pdf_async(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: PdfMode,
+    ) -> TessResult<PdfOutput> {
+        let mut command = self
+            .create_tesseract_list_command_async(images, "stdout", &pdf_args(args, mode))
+            .await?;
+        command.arg("pdf");
+
+        let output = self.run_tesseract_command_bytes_async(command).await?;
+        Ok(PdfOutput { output })
+    }
+
+    pub async fn image_to_pdf_file_async<P: AsRef<Path>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        mode: PdfMode,
+        output_base: P,
+    ) -> TessResult<PathBuf> {
+        let output_base = &self.resolve_path(output_base.as_ref());
+        let started = Instant::now();
+        let args = pdf_args(args, mode);
+        let upright = self.prepare_async(image, &args).await?;
+        let image_libraries = self.image_libraries_async().await;
+        let mut command = self.upright_image_command(
+            image,
+            upright,
+            output_base,
+            &args,
+            started,
+            &image_libraries,
+        )?;
+        command.arg("pdf");
+
+        self.run_tesseract_command_bytes_async(command).await?;
+        Ok(output_path(output_base, OutputKind::Pdf))
+    }
+
+    pub async fn images_to_pdf_file_async<P: AsRef<Path>>(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: PdfMode,
+        output_base: P,
+    ) -> TessResult<PathBuf> {
+        let output_base = &self.resolve_path(output_base.as_ref());
+        let mut command = self
+            .create_tesseract_list_command_async(images, output_base, &pdf_args(args, mode))
+            .await?;
+        command.arg("pdf");
+
+        self.run_tesseract_command_bytes_async(command).await?;
+        Ok(output_path(output_base, OutputKind::Pdf))
+    }
+
+    pub async fn image_to_outputs_async(
+        &self,
+        image: &Image,
+        args: &Args,
+        kinds: &[OutputKind],
+    ) -> TessResult<CombinedOutput> {
+        let output_dir = output_tempdir()?;
+        let output_base = output_dir.path().join("output");
+        let started = Instant::now();
+        let upright = self.prepare_async(image, args).await?;
+        let image_libraries = self.image_libraries_async().await;
+        let mut command = self.upright_image_command(
+            image,
+            upright,
+            &output_base,
+            args,
+            started,
+            &image_libraries,
+        )?;
+        command.args(unique(kinds).map(|x| x.config()));
+        let rotation = command.rotation;
+
+        self.run_tesseract_command_bytes_async(command).await?;
+        Ok(CombinedOutput {
+            rotation,
+            ..read_outputs(&output_base, kinds)?
+        })
+    }
+
+    pub async fn image_to_regions_async(
+        &self,
+        image: &Image,
+        regions: &[Region],
+        args: &Args,
+        kinds: &[OutputKind],
+    ) -> TessResult<Vec<RegionOutput>> {
+        let owned = regions.to_vec();
+        let (crops, image_height) = self
+            .with_resolved_blocking(image, move |x| crop_regions(x, &owned))
+            .await?;
+
+        let mut results = Vec::with_capacity(regions.len());
+        for (region, crop) in regions.iter().zip(crops) {
+            let output = self
+                .image_to_outputs_async(&crop, &region_args(region, args), kinds)
+                .await?;
+            results.push(translate_output(region, output, image_height)?);
+        }
+
+        Ok(results)
+    }
+
+    pub async fn image_to_string_pages_async<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<String>>> {
+        let page_count = self
+            .with_resolved_blocking(image, |x| x.page_count())
+            .await?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_string_async(image, args).await?;
+            return string_pages(&output, page_count, ..);
+        };
+
+        let mut results = Vec::with_capacity(selected.len());
+        for page in selected {
+            let output = self
+                .image_to_string_async(image, &page_args(args, page))
+                .await?;
+            results.push(single_page_string(&output, page)?);
+        }
+
+        Ok(results)
+    }
+
+    pub async fn image_to_data_pages_async<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<DataOutput>>> {
+        let page_count = self
+            .with_resolved_blocking(image, |x| x.page_count())
+            .await?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_data_async(image, args).await?;
+            return data_pages(&output, page_count, ..);
+        };
+
+        let mut results = Vec::with_capacity(selected.len());
+        for page in selected {
+            let output = self
+                .image_to_data_async(image, &page_args(args, page))
+                .await?;
+            results.push(single_page_data(output, page)?);
+        }
+
+        Ok(results)
+    }
+
+    pub async fn image_to_boxes_pages_async<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<BoxOutput>>> {
+        let page_count = self
+            .with_resolved_blocking(image, |x| x.page_count())
+            .await?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_boxes_async(image, args).await?;
+            return box_pages(&output, page_count, ..);
+        };
+
+        let mut results = Vec::with_capacity(selected.len());
+        for page in selected {
+            let output = self
+                .image_to_boxes_async(image, &page_args(args, page))
+                .await?;
+            results.push(single_page_boxes(output, page));
+        }
+
+        Ok(results)
+    }
+
+    pub async fn image_orientation_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<Orientation> {
+        // built directly, as `create_tesseract_command_async` would recurse into OSD
+        let preprocessed = Upright {
+            image: self.preprocessed_async(image).await?,
+            ..Upright::default()
+        };
+        let image_libraries = self.image_libraries_async().await;
+        let command = self.upright_image_command(
+            image,
+            preprocessed,
+            "stdout",
+            &osd_args(args),
+            Instant::now(),
+            &image_libraries,
+        )?;
+        let output = self.run_tesseract_command_async(command).await?;
+
+        string_to_orientation(output)
+    }
+
+    pub async fn image_upright_async(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        let page_count = self
+            .with_resolved_blocking(image, |x| x.page_count())
+            .await?;
+        if page_count > 1 {
+            return Ok(Upright::default());
+        }
+
+        let orientation = self.image_orientation_async(image, args).await?;
+        self.with_resolved_blocking(image, move |x| orientation.rotate_image(x))
+            .await
+    }
+
+    /// See `images_to_string`. In `BatchMode::Parallel`, up to `workers` processes
+    /// run at a time, the tasks are polled on the current task.
+    pub async fn images_to_string_async(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: BatchMode,
+    ) -> Vec<TessResult<String>> {
+        match mode {
+            BatchMode::ListFile => {
+                let output = match self
+                    .create_tesseract_list_command_async(images, "stdout", args)
+                    .await
+                {
+                    Ok(command) => self.run_tesseract_command_async(command).await,
+                    Err(e) => Err(e),
+                };
+                let output = match output {
+                    Ok(output) => {
+                        let shared = images.iter().map(Image::share).collect::<Vec<_>>();
+                        let engine = self.clone();
+                        spawn_blocking(move || engine.page_counts(&shared))
+                            .await
+                            .and_then(|page_counts| group_pages(&output, &page_counts))
+                    }
+                    Err(e) => Err(e),
+                };
+
+                match output {
+                    Ok(texts) => texts.into_iter().map(Ok).collect(),
+                    Err(e) if falls_back(&e) => {
+                        let mut results = Vec::with_capacity(images.len());
+                        for image in images {
+                            let output = self.image_to_string_async(image, args).await;
+                            results.push(output.map(image_text));
+                        }
+                        results
+                    }
+                    Err(e) => images.iter().map(|_| Err(e.clone())).collect(),
+                }
+            }
+            BatchMode::Parallel {
+                workers,
+                omp_thread_limit,
+            } => {
+                let mut engine = self.clone();
+                if let Some(limit) = omp_thread_limit {
+                    engine
+                        .envs
+                        .insert("OMP_THREAD_LIMIT".into(), limit.to_string());
+                }
+
+                let next = AtomicUsize::new(0);
+                let worker = || async {
+                    let mut results = Vec::new();
+                    loop {
+                        let index = next.fetch_add(1, Ordering::SeqCst);
+                        if index >= images.len() {
+                            break results;
+                        }
+                        let output = engine.image_to_string_async(&images[index], args).await;
+                        results.push((index, output.map(image_text)));
+                    }
+                };
+                let workers = (0..workers.clamp(1, images.len().max(1)))
+                    .map(|_| worker())
+                    .collect::<Vec<_>>();
+
+                let mut results = join_all(workers)
+                    .await
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>();
+                results.sort_by_key(|x| x.0);
+                results.into_iter().map(|x| x.1).collect()
+            }
+        }
+    }
+
+    /// Runs `f` with the resolved image on the blocking thread pool,
+    /// as decoding and encoding images would block the executor.
+    async fn with_resolved_blocking<T, F>(&self, image: &Image, f: F) -> TessResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Image) -> TessResult<T> + Send + 'static,
+    {
+        let image = self.with_resolved(image, Image::share);
+        spawn_blocking(move || f(&image)).await
+    }
+
+    async fn image_libraries_async(&self) -> Vec<String> {
+        match cached_image_libraries(self) {
+            Some(libraries) => libraries,
+            None => cache_image_libraries(self, self.get_tesseract_version_async().await),
+        }
+    }
+
+    async fn auto_rotate_async(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        if !args.auto_rotate {
+            return Ok(Upright::default());
+        }
+
+        self.image_upright_async(image, args).await
+    }
+
+    /// Like `prepare`, with the preprocessing on the blocking thread pool.
+    async fn prepare_async(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        let preprocessed = self.preprocessed_async(image).await?;
+        let upright = self
+            .auto_rotate_async(preprocessed.as_ref().unwrap_or(image), args)
+            .await?;
+
+        Ok(Upright {
+            image: upright.image.or(preprocessed),
+            ..upright
+        })
+    }
+
+    async fn preprocessed_async(&self, image: &Image) -> TessResult<Option<Image>> {
+        if image.preprocess.is_none() {
+            return Ok(None);
+        }
+
+        self.with_resolved_blocking(image, Image::preprocessed)
+            .await
+    }
+
+    /// Preprocesses the image and runs OSD for `args.auto_rotate` asynchronously,
+    /// so that `create_tesseract_command` does not block while preparing the image.
+    pub(crate) async fn create_tesseract_command_async(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        let started = Instant::now();
+        let upright = self.prepare_async(image, args).await?;
+        let image_libraries = self.image_libraries_async().await;
+        self.upright_image_command(image, upright, "stdout", args, started, &image_libraries)
+    }
+
+    /// Like `create_tesseract_command_async` for `create_tesseract_list_command`.
+    async fn create_tesseract_list_command_async<O: AsRef<OsStr>>(
+        &self,
+        images: &[Image],
+        output: O,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        let started = Instant::now();
+        let mut upright = Vec::new();
+        for image in images {
+            upright.push(self.prepare_async(image, args).await?);
+        }
+
+        let image_libraries = self.image_libraries_async().await;
+        self.upright_list_command(
+            images.iter().collect(),
+            upright,
+            output,
+            args,
+            started,
+            &image_libraries,
+        )
+    }
+
+    pub(crate) async fn run_tesseract_command_async<C: Into<TesseractCommand>>(
+        &self,
+        command: C,
+    ) -> TessResult<String> {
+        let output = self.run_tesseract_command_bytes_async(command).await?;
+        Ok(lossy_string(output))
+    }
+
+    pub(crate) async fn run_tesseract_command_bytes_async<C: Into<TesseractCommand>>(
+        &self,
+        command: C,
+    ) -> TessResult<Vec<u8>> {
+        // the tempfiles have to outlive the tesseract process
+        let TesseractCommand {
+            mut command,
+            tempfiles: _tempfiles,
+            stdin,
+            started,
+            ..
+        } = command.into();
+        prepare_command(&mut command);
+        if stdin.is_some() {
+            command.stdin(Stdio::piped());
+        }
+
+        if self.is_cancelled() {
+            return Err(TessError::Cancelled(String::new()));
+        }
+
+        let mut command = tokio::process::Command::from(command);
+        let mut child = command
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|_| TessError::TesseractNotFoundError)?;
+
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or(TessError::TesseractNotFoundError)?;
+        let mut stderr = child
+            .stderr
+            .take()
+            .ok_or(TessError::TesseractNotFoundError)?;
+        let stdin_pipe = child.stdin.take();
+        let write_stdin = async {
+            if let (Some(mut pipe), Some(bytes)) = (stdin_pipe, stdin) {
+                // write errors surface as exit status of tesseract, dropping the pipe closes stdin
+                let _ = pipe.write_all(&bytes).await;
+            }
+            Ok(())
+        };
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+
+        // the buffers keep everything read so far when the process future is dropped,
+        // so the partial stderr is still available after a timeout or cancellation
+        let result = tokio::select! {
+            biased;
+            result = async {
+                tokio::try_join!(
+                    stdout.read_to_end(&mut out),
+                    stderr.read_to_end(&mut err),
+                    write_stdin,
+                    child.wait(),
+                )
+            } => Some(result.map_err(|_| TessError::TesseractNotFoundError)?.3),
+            _ = self.timed_out(started.unwrap_or_else(Instant::now)) => None,
+            _ = self.cancelled() => None,
+        };
+
+        match result {
+            Some(status) => check_exit_status(status, out, lossy_string(err)),
+            None => {
+                // the process may have exited in the meantime, so errors are ignored here
+                let _ = child.kill().await;
+                let err = String::from_utf8_lossy(&err).into_owned();
+
+                match self.timeout {
+                    Some(timeout) if !self.is_cancelled() => Err(TessError::Timeout(timeout, err)),
+                    _ => Err(TessError::Cancelled(err)),
+                }
+            }
+        }
+    }
+
+    async fn timed_out(&self, start: Instant) {
+        match self.timeout {
+            Some(timeout) => tokio::time::sleep_until((start + timeout).into()).await,
+            None => future::pending().await,
+        }
+    }
+
+    async fn cancelled(&self) {
+        match &self.cancellation {
+            Some(token) => token.cancelled().await,
+            None => future::pending().await,
+        }
+    }
+}
+
+/// Runs `f` on tokio's blocking thread pool and resumes a panic of `f`.
+async fn spawn_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(result) => result,
+        Err(e) => panic::resume_unwind(e.into_panic()),
+    }
+}
+
+/// Polls all futures concurrently and returns their outputs in the order of `futures`.
+async fn join_all<F: Future>(futures: Vec<F>) -> Vec<F::Output> {
+    let mut futures = futures
+        .into_iter()
+        .map(std::boxed::Box::pin)
+        .collect::<Vec<_>>();
+    let mut outputs = futures.iter().map(|_| None).collect::<Vec<_>>();
+
+    future::poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            if output.is_none() {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(x) => *output = Some(x),
+                    Poll::Pending => pending = true,
+                }
+            }
+        }
+
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    })
+    .await;
+
+    outputs
+        .into_iter()
+        .map(|x| x.expect("every future is polled to completion"))
+        .collect()
+}
+
+pub async fn get_tesseract_version_async() -> TessResult<TesseractVersion> {
+    TesseractEngine::default()
+        .get_tesseract_version_async()
+        .await
+}
+
+pub async fn require_tesseract_version_async(
+    major: u32,
+    minor: u32,
+    patch: u32,
+) -> TessResult<TesseractVersion> {
+    TesseractEngine::default()
+        .require_tesseract_version_async(major, minor, patch)
+        .await
+}
+
+pub async fn get_tesseract_langs_async() -> TessResult<Vec<String>> {
+    TesseractEngine::default().get_tesseract_langs_async().await
+}
+
+pub async fn get_tesseract_langs_for_async(args: &Args) -> TessResult<Vec<String>> {
+    TesseractEngine::default()
+        .get_tesseract_langs_for_async(args)
+        .await
+}
+
+pub async fn get_tesseract_config_parameters_async() -> TessResult<ConfigParameterOutput> {
+    TesseractEngine::default()
+        .get_tesseract_config_parameters_async()
+        .await
+}
+
+pub async fn image_to_string_async(image: &Image, args: &Args) -> TessResult<String> {
+    TesseractEngine::default()
+        .image_to_string_async(image, args)
+        .await
+}
+
+pub async fn image_to_boxes_async(image: &Image, args: &Args) -> TessResult<BoxOutput> {
+    TesseractEngine::default()
+        .image_to_boxes_async(image, args)
+        .await
+}
+
+pub async fn image_to_data_async(image: &Image, args: &Args) -> TessResult<DataOutput> {
+    TesseractEngine::default()
+        .image_to_data_async(image, args)
+        .await
+}
+
+pub async fn image_to_hocr_async(image: &Image, args: &Args) -> TessResult<HocrOutput> {
+    TesseractEngine::default()
+        .image_to_hocr_async(image, args)
+        .await
+}
+
+pub async fn image_to_alto_async(image: &Image, args: &Args) -> TessResult<AltoOutput> {
+    TesseractEngine::default()
+        .image_to_alto_async(image, args)
+        .await
+}
+
+pub async fn image_to_pdf_async(
+    image: &Image,
+    args: &Args,
+    mode: PdfMode,
+) -> TessResult<PdfOutput> {
+    TesseractEngine::default()
+        .image_to_pdf_async(image, args, mode)
+        .await
+}
+
+pub async fn images_to_pdf_async(
+    images: &[Image],
+    args: &Args,
+    mode: PdfMode,
+) -> TessResult<PdfOutput> {
+    TesseractEngine::default()
+        .images_to_pdf_async(images, args, mode)
+        .await
+}
+
+pub async fn image_to_pdf_file_async<P: AsRef<Path>>(
+    image: &Image,
+    args: &Args,
+    mode: PdfMode,
+    output_base: P,
+) -> TessResult<PathBuf> {
+    TesseractEngine::default()
+        .image_to_pdf_file_async(image, args, mode, output_base)
+        .await
+}
+
+pub async fn images_to_pdf_file_async<P: AsRef<Path>>(
+    images: &[Image],
+    args: &Args,
+    mode: PdfMode,
+    output_base: P,
+) -> TessResult<PathBuf> {
+    TesseractEngine::default()
+        .images_to_pdf_file_async(images, args, mode, output_base)
+        .await
+}
+
+pub async fn image_to_outputs_async(
+    image: &Image,
+    args: &Args,
+    kinds: &[OutputKind],
+) -> TessResult<CombinedOutput> {
+    TesseractEngine::default()
+        .image_to_outputs_async(image, args, kinds)
+        .await
+}
+
+pub async fn image_to_regions_async(
+    image: &Image,
+    regions: &[Region],
+    args: &Args,
+    kinds: &[OutputKind],
+) -> TessResult<Vec<RegionOutput>> {
+    TesseractEngine::default()
+        .image_to_regions_async(image, regions, args, kinds)
+        .await
+}
+
+pub async fn image_orientation_async(image: &Image, args: &Args) -> TessResult<Orientation> {
+    TesseractEngine::default()
+        .image_orientation_async(image, args)
+        .await
+}
+
+pub async fn image_upright_async(image: &Image, args: &Args) -> TessResult<Upright> {
+    TesseractEngine::default()
+        .image_upright_async(image, args)
+        .await
+}
+
+pub async fn images_to_string_async(
+    images: &[Image],
+    args: &Args,
+    mode: BatchMode,
+) -> Vec<TessResult<String>> {
+    TesseractEngine::default()
+        .images_to_string_async(images, args, mode)
+        .await
+}
+
+pub async fn image_to_string_pages_async<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<String>>> {
+    TesseractEngine::default()
+        .image_to_string_pages_async(image, args, pages)
+        .await
+}
+
+pub async fn image_to_data_pages_async<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<DataOutput>>> {
+    TesseractEngine::default()
+        .image_to_data_pages_async(image, args, pages)
+        .await
+}
+
+pub async fn image_to_boxes_pages_async<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<BoxOutput>>> {
+    TesseractEngine::default()
+        .image_to_boxes_pages_async(image, args, pages)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{asynchronous::join_all, *};
+    use std::process::Command;
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn test_image_to_string_async() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let output = image_to_string_async(&img, &Args::default()).await.unwrap();
+
+        assert_eq!(output.trim(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+
+    #[tokio::test]
+    async fn test_engine_not_found_async() {
+        let engine = TesseractEngine::new("./does-not-exist/tesseract");
+
+        assert_eq!(
+            engine.get_tesseract_version_async().await,
+            Err(TessError::TesseractNotFoundError)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_images_to_string_async_engine_not_found() {
+        let engine = TesseractEngine::new("./does-not-exist/tesseract");
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        for mode in [BatchMode::ListFile, BatchMode::parallel()] {
+            let results = engine
+                .images_to_string_async(&images, &Args::default(), mode)
+                .await;
+            assert_eq!(
+                results,
+                vec![
+                    Err(TessError::TesseractNotFoundError),
+                    Err(TessError::TesseractNotFoundError)
+                ]
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_join_all() {
+        let futures = (0..3)
+            .map(|index| async move {
+                // later futures finish first
+                for _ in index..3 {
+                    tokio::task::yield_now().await;
+                }
+                index
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(join_all(futures).await, vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_image_to_string_pages_async_image_not_found() {
+        let img = Image::from_path("img/does-not-exist.tiff").unwrap();
+
+        let result = image_to_string_pages_async(&img, &Args::default(), ..).await;
+
+        assert_eq!(result, Err(TessError::ImageNotFoundError));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_with_stdin_async() {
+        let mut command = TesseractCommand::from(Command::new("cat"));
+        command.stdin = Some(b"LOREM IPSUM".to_vec());
+
+        let result = TesseractEngine::default()
+            .run_tesseract_command_async(command)
+            .await;
+
+        assert_eq!(result, Ok("LOREM IPSUM".into()));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_timeout_async() {
+        let engine = TesseractEngine {
+            timeout: Some(Duration::from_millis(50)),
+            ..TesseractEngine::default()
+        };
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("echo started >&2; sleep 5");
+
+        let start = Instant::now();
+        let result = engine.run_tesseract_command_async(command).await;
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            result,
+            Err(TessError::Timeout(
+                Duration::from_millis(50),
+                "started\n".into()
+            ))
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancellation_async() {
+        let token = CancellationToken::new();
+        let engine = TesseractEngine {
+            cancellation: Some(token.clone()),
+            ..TesseractEngine::default()
+        };
+        let mut command = Command::new("sleep");
+        command.arg("5");
+
+        let canceller = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            token.cancel();
+        };
+        let (result, _) = tokio::join!(engine.run_tesseract_command_async(command), canceller);
+
+        assert_eq!(result, Err(TessError::Cancelled(String::new())));
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_token_cancelled() {
+        let token = CancellationToken::new();
+
+        let canceller = async {
+            tokio::task::yield_now().await;
+            token.cancel();
+        };
+        tokio::join!(token.cancelled(), canceller);
+
+        // completes immediately once the token is cancelled
+        token.cancelled().await;
+    }
+}
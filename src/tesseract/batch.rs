@@ -0,0 +1,375 @@
+use super::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// How `images_to_string` distributes a batch of images over tesseract processes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchMode {
+    /// A single tesseract run reading all images from a list file.
+    ///
+    /// Avoids the process startup per image. The output is split at the form feeds after
+    /// each page and grouped by the `Image::page_count` of every image, so multi-page TIFFs
+    /// are supported. If the run fails because of an image, e.g. an unreadable one,
+    /// every image is recognized in a run of its own instead, so that only the bad images
+    /// report an error. Other errors, like a timeout or a cancellation, are returned for
+    /// every image without further runs.
+    ListFile,
+    /// One tesseract run per image, with up to `workers` processes at a time.
+    ///
+    /// `omp_thread_limit` is passed as `OMP_THREAD_LIMIT`, as tesseract's own
+    /// multithreading slows down considerably when several processes compete for the CPUs.
+    Parallel {
+        workers: usize,
+        omp_thread_limit: Option<u32>,
+    },
+}
+
+impl BatchMode {
+    /// One worker per available CPU, each limited to a single OpenMP thread.
+    pub fn parallel() -> Self {
+        BatchMode::Parallel {
+            workers: thread::available_parallelism().map_or(1, |x| x.get()),
+            omp_thread_limit: Some(1),
+        }
+    }
+}
+
+impl TesseractEngine {
+    /// Recognizes all images and returns one result per image, in the order of `images`.
+    ///
+    /// Unlike `image_to_string`, the form feed after the last page is removed in every mode.
+    /// The pages of a multi-page image stay separated by form feeds.
+    pub fn images_to_string(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: BatchMode,
+    ) -> Vec<TessResult<String>> {
+        match mode {
+            BatchMode::ListFile => {
+                let output = self
+                    .create_tesseract_list_command(images, "stdout", args)
+                    .and_then(|command| self.run_tesseract_command(command))
+                    .and_then(|output| group_pages(&output, &self.page_counts(images)?));
+
+                match output {
+                    Ok(texts) => texts.into_iter().map(Ok).collect(),
+                    Err(e) if falls_back(&e) => images
+                        .iter()
+                        .map(|image| self.image_to_string(image, args).map(image_text))
+                        .collect(),
+                    Err(e) => images.iter().map(|_| Err(e.clone())).collect(),
+                }
+            }
+            BatchMode::Parallel {
+                workers,
+                omp_thread_limit,
+            } => {
+                let mut engine = self.clone();
+                if let Some(limit) = omp_thread_limit {
+                    engine
+                        .envs
+                        .insert("OMP_THREAD_LIMIT".into(), limit.to_string());
+                }
+
+                run_parallel(images.len(), workers, |index| {
+                    engine.image_to_string(&images[index], args).map(image_text)
+                })
+            }
+        }
+    }
+
+    pub(crate) fn page_counts(&self, images: &[Image]) -> TessResult<Vec<u32>> {
+        images
+            .iter()
+            .map(|image| self.with_resolved(image, Image::page_count))
+            .collect()
+    }
+}
+
+pub fn images_to_string(images: &[Image], args: &Args, mode: BatchMode) -> Vec<TessResult<String>> {
+    TesseractEngine::default().images_to_string(images, args, mode)
+}
+
+/// Splits text output at the form feeds tesseract writes after each page,
+/// e.g. of a list file run or a multi-page TIFF.
+pub(crate) fn split_pages(output: &str, count: usize) -> TessResult<Vec<String>> {
+    let output = output.strip_suffix('\x0c').unwrap_or(output);
+    let pages = output.split('\x0c').collect::<Vec<_>>();
+
+    if pages.len() == count {
+        Ok(pages.into_iter().map(|x| x.into()).collect())
+    } else {
+        Err(TessError::ParseError(format!(
+            "text output with {} pages, expected {}",
+            pages.len(),
+            count
+        )))
+    }
+}
+
+/// Splits the output of a list file run into the texts of the images with `page_counts` pages.
+pub(crate) fn group_pages(output: &str, page_counts: &[u32]) -> TessResult<Vec<String>> {
+    let total = page_counts.iter().map(|x| *x as usize).sum();
+    let mut pages = split_pages(output, total)?.into_iter();
+
+    Ok(page_counts
+        .iter()
+        .map(|count| {
+            pages
+                .by_ref()
+                .take(*count as usize)
+                .collect::<Vec<_>>()
+                .join("\x0c")
+        })
+        .collect())
+}
+
+/// Removes the form feed after the last page of the output of a single image.
+pub(crate) fn image_text(output: String) -> String {
+    match output.strip_suffix('\x0c') {
+        Some(text) => text.into(),
+        None => output,
+    }
+}
+
+/// Whether a failed list file run is caused by one of the images,
+/// so that recognizing the images one by one gives results for the others.
+pub(crate) fn falls_back(error: &TessError) -> bool {
+    matches!(
+        error,
+        TessError::CommandExitStatusError(..)
+            | TessError::ParseError(_)
+            | TessError::ImageFormatError(..)
+            | TessError::ImageNotFoundError
+            | TessError::ImageDecodeError(_)
+    )
+}
+
+/// Calls `f` for every index in `0..count` on up to `workers` threads
+/// and returns the results in index order.
+pub(crate) fn run_parallel<T: Send>(
+    count: usize,
+    workers: usize,
+    f: impl Fn(usize) -> T + Sync,
+) -> Vec<T> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new((0..count).map(|_| None).collect::<Vec<_>>());
+
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, count.max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                if index >= count {
+                    break;
+                }
+                let result = f(index);
+                results.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|x| x.expect("every index is processed by a worker"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        batch::{group_pages, image_text, run_parallel, split_pages},
+        *,
+    };
+    #[cfg(unix)]
+    use std::path::{Path, PathBuf};
+    #[cfg(unix)]
+    use std::time::Duration;
+
+    #[test]
+    fn test_split_pages() {
+        assert_eq!(
+            split_pages("LOREM\n\x0cIPSUM\n\x0c", 2),
+            Ok(vec!["LOREM\n".to_string(), "IPSUM\n".to_string()])
+        );
+        assert_eq!(
+            split_pages("LOREM\n\x0cIPSUM\n\x0c", 3),
+            Err(TessError::ParseError(
+                "text output with 2 pages, expected 3".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_group_pages() {
+        assert_eq!(
+            group_pages("A\n\x0cB\n\x0cC\n\x0c", &[2, 1]),
+            Ok(vec!["A\n\x0cB\n".to_string(), "C\n".to_string()])
+        );
+        assert_eq!(
+            group_pages("A\n\x0cB\n\x0c", &[1, 2]),
+            Err(TessError::ParseError(
+                "text output with 2 pages, expected 3".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_image_text() {
+        assert_eq!(image_text("A\n\x0cB\n\x0c".into()), "A\n\x0cB\n");
+        assert_eq!(image_text("A\n".into()), "A\n");
+    }
+
+    #[test]
+    fn test_run_parallel() {
+        let results = run_parallel(10, 3, |index| index * 2);
+
+        assert_eq!(results, (0..10).map(|x| x * 2).collect::<Vec<_>>());
+        assert!(run_parallel(0, 3, |index| index).is_empty());
+    }
+
+    #[test]
+    fn test_images_to_string_engine_not_found() {
+        let engine = TesseractEngine::new("tesseract-does-not-exist");
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        for mode in [BatchMode::ListFile, BatchMode::parallel()] {
+            let results = engine.images_to_string(&images, &Args::default(), mode);
+            assert_eq!(
+                results,
+                vec![
+                    Err(TessError::TesseractNotFoundError),
+                    Err(TessError::TesseractNotFoundError)
+                ]
+            );
+        }
+    }
+
+    /// Writes a shell script that runs the `case` branches for the input file in `$1`
+    /// and prints one page otherwise.
+    #[cfg(unix)]
+    fn fake_tesseract(dir: &Path, cases: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let executable = dir.join("tesseract");
+        std::fs::write(
+            &executable,
+            format!(
+                "#!/bin/sh\ncase \"$1\" in\n{}\nesac\nprintf 'LOREM\\n\\f'\n",
+                cases
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        executable
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_file_falls_back_to_single_runs() {
+        let dir = tempfile::tempdir().unwrap();
+        // fails the list file run and every run of the corrupt image
+        let executable = fake_tesseract(
+            dir.path(),
+            "*.txt) echo 'Error in list' >&2; exit 1;;\n\
+            *corrupt*) echo 'Error in pixReadStream' >&2; exit 1;;",
+        );
+        let corrupt = dir.path().join("corrupt.png");
+        std::fs::write(&corrupt, b"\x89PNG\r\n\x1a\n truncated").unwrap();
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path(&corrupt).unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        let results = TesseractEngine::new(&executable).images_to_string(
+            &images,
+            &Args::default(),
+            BatchMode::ListFile,
+        );
+
+        assert_eq!(
+            results,
+            vec![
+                Ok("LOREM\n".to_string()),
+                Err(TessError::CommandExitStatusError(
+                    "exit status: 1".into(),
+                    "Error in pixReadStream\n".into()
+                )),
+                Ok("LOREM\n".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_file_timeout_without_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable = fake_tesseract(dir.path(), "*.txt) exec sleep 5;;");
+        let engine = TesseractEngine {
+            timeout: Some(Duration::from_millis(50)),
+            ..TesseractEngine::new(&executable)
+        };
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        let results = engine.images_to_string(&images, &Args::default(), BatchMode::ListFile);
+
+        let timeout = TessError::Timeout(Duration::from_millis(50), String::new());
+        assert_eq!(results, vec![Err(timeout.clone()), Err(timeout)]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_list_file_with_multi_page_tiff() {
+        let dir = tempfile::tempdir().unwrap();
+        let executable =
+            fake_tesseract(dir.path(), "*.txt) printf 'A\\n\\fB\\n\\fC\\n\\f'; exit;;");
+        let tiff = dir.path().join("pages.tiff");
+        let mut encoder =
+            tiff::encoder::TiffEncoder::new(std::fs::File::create(&tiff).unwrap()).unwrap();
+        for _ in 0..2 {
+            encoder
+                .write_image::<tiff::encoder::colortype::Gray8>(2, 2, &[0, 255, 255, 0])
+                .unwrap();
+        }
+        let images = [
+            Image::from_path(&tiff).unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        let results = TesseractEngine::new(&executable).images_to_string(
+            &images,
+            &Args::default(),
+            BatchMode::ListFile,
+        );
+
+        assert_eq!(
+            results,
+            vec![Ok("A\n\x0cB\n".to_string()), Ok("C\n".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_images_to_string() {
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        for mode in [BatchMode::ListFile, BatchMode::parallel()] {
+            let results = images_to_string(&images, &Args::default(), mode);
+            for result in results {
+                assert_eq!(result.unwrap().trim(), "LOREM IPSUM DOLOR SIT AMET");
+            }
+        }
+    }
+}
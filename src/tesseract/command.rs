@@ -1,6 +1,15 @@
 use super::*;
-use std::process::{Command, Stdio};
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::io::{Read, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
 use std::string::ToString;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use crate::error::{TessError, TessResult};
 
@@ -10,33 +19,547 @@ use std::os::windows::process::CommandExt;
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
-pub(crate) fn get_tesseract_command() -> Command {
-    let tesseract = if cfg!(target_os = "windows") {
-        "tesseract.exe"
-    } else {
-        "tesseract"
-    };
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
 
-    Command::new(tesseract)
+/// Describes how the tesseract executable is invoked.
+///
+/// The free functions of this crate (`image_to_string`, `get_tesseract_langs`, ...)
+/// use `TesseractEngine::default()`, which looks up `tesseract` on the `PATH`.
+/// Construct your own engine to use a vendored build, run several versions side by side
+/// or pass environment variables such as `TESSDATA_PREFIX` or `OMP_THREAD_LIMIT`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TesseractEngine {
+    /// path or name of the tesseract executable
+    pub executable: PathBuf,
+    /// additional environment variables passed to the tesseract process
+    pub envs: HashMap<String, String>,
+    /// working directory of the tesseract process (relative image paths are resolved against it)
+    pub current_dir: Option<PathBuf>,
+    /// directory containing the `.traineddata` files, passed as `--tessdata-dir`
+    pub tessdata_dir: Option<PathBuf>,
+    /// kill the tesseract process if it runs longer than this
+    pub timeout: Option<Duration>,
+    /// kill the tesseract process as soon as this token is cancelled
+    pub cancellation: Option<CancellationToken>,
 }
 
-pub fn get_tesseract_version() -> TessResult<String> {
-    let mut command = get_tesseract_command();
-    command.arg("--version");
+impl Default for TesseractEngine {
+    fn default() -> Self {
+        let executable = if cfg!(target_os = "windows") {
+            "tesseract.exe"
+        } else {
+            "tesseract"
+        };
 
-    run_tesseract_command(&mut command)
+        TesseractEngine {
+            executable: executable.into(),
+            envs: HashMap::new(),
+            current_dir: None,
+            tessdata_dir: None,
+            timeout: None,
+            cancellation: None,
+        }
+    }
+}
+
+impl TesseractEngine {
+    pub fn new<P: Into<PathBuf>>(executable: P) -> Self {
+        TesseractEngine {
+            executable: executable.into(),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn command(&self) -> Command {
+        self.command_with_tessdata_dir(self.tessdata_dir.as_deref())
+    }
+
+    /// Creates a command using `tessdata_dir`, falling back to the one of the engine.
+    pub(crate) fn command_in(&self, tessdata_dir: Option<&Path>) -> Command {
+        self.command_with_tessdata_dir(tessdata_dir.or(self.tessdata_dir.as_deref()))
+    }
+
+    fn command_with_tessdata_dir(&self, tessdata_dir: Option<&Path>) -> Command {
+        let mut command = Command::new(&self.executable);
+        command.envs(&self.envs);
+
+        if let Some(current_dir) = &self.current_dir {
+            command.current_dir(current_dir);
+        }
+
+        if let Some(tessdata_dir) = tessdata_dir {
+            command.arg("--tessdata-dir").arg(tessdata_dir);
+        }
+
+        command
+    }
+
+    pub fn get_tesseract_langs(&self) -> TessResult<Vec<String>> {
+        let mut command = self.command();
+        command.arg("--list-langs");
+
+        let output = self.run_tesseract_command(command)?;
+        Ok(parse_langs(&output))
+    }
+
+    /// Lists the languages available in the tessdata directory of `args`.
+    pub fn get_tesseract_langs_for(&self, args: &Args) -> TessResult<Vec<String>> {
+        self.langs_in(args.tessdata_dir.as_deref())
+    }
+
+    /// Lists the languages available in `tessdata_dir`, falling back to the one of the engine.
+    pub(crate) fn langs_in(&self, tessdata_dir: Option<&Path>) -> TessResult<Vec<String>> {
+        let mut command = self.command_in(tessdata_dir);
+        command.arg("--list-langs");
+
+        let output = self.run_tesseract_command(command)?;
+        Ok(parse_langs(&output))
+    }
+
+    pub fn image_to_string(&self, image: &Image, args: &Args) -> TessResult<String> {
+        let command = self.create_tesseract_command(image, args)?;
+        let output = self.run_tesseract_command(command)?;
+
+        Ok(output)
+    }
+
+    pub(crate) fn create_tesseract_command(
+        &self,
+        image: &Image,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        self.create_image_command(image, "stdout", args)
+    }
+
+    /// Like `create_tesseract_command`, but lets tesseract write its output files
+    /// to `output_base` with the extension of each config instead of `stdout`.
+    pub(crate) fn create_tesseract_command_with_output(
+        &self,
+        image: &Image,
+        output_base: &Path,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        self.create_image_command(image, output_base, args)
+    }
+
+    /// Like `create_image_command`, but reads the images listed in a text file,
+    /// one path per line. The list file is kept alive together with the command.
+    pub(crate) fn create_tesseract_list_command<'a, O: AsRef<OsStr>>(
+        &self,
+        images: impl IntoIterator<Item = &'a Image>,
+        output: O,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        let started = Instant::now();
+        let images = images.into_iter().collect::<Vec<_>>();
+        let upright = images
+            .iter()
+            .map(|image| self.prepare(image, args))
+            .collect::<TessResult<Vec<_>>>()?;
+        let image_libraries = self.image_libraries();
+        self.upright_list_command(images, upright, output, args, started, &image_libraries)
+    }
+
+    /// Creates the list command for images prepared by `prepare` or `prepare_async`.
+    pub(crate) fn upright_list_command<O: AsRef<OsStr>>(
+        &self,
+        images: Vec<&Image>,
+        upright: Vec<Upright>,
+        output: O,
+        args: &Args,
+        started: Instant,
+        image_libraries: &[String],
+    ) -> TessResult<TesseractCommand> {
+        let inputs = images
+            .iter()
+            .zip(&upright)
+            .map(|(image, upright)| upright.image.as_ref().unwrap_or(image))
+            .collect::<Vec<_>>();
+        for input in &inputs {
+            self.check_image_format(input, image_libraries)?;
+        }
+        let paths = inputs
+            .iter()
+            .map(|input| input.get_image_path())
+            .collect::<TessResult<Vec<_>>>()?;
+        if paths.is_empty() {
+            return Err(TessError::ImageNotFoundError);
+        }
+
+        let list = image_list_tempfile(&paths)?;
+        let mut command = self.create_tesseract_command_for_input(list.path(), output, args)?;
+        command.tempfiles.push(list);
+        command.tempfiles.extend(
+            upright
+                .into_iter()
+                .filter_map(|x| x.image)
+                .filter_map(Image::into_tempfile),
+        );
+        command.started = Some(started);
+
+        Ok(command)
+    }
+
+    /// Preprocesses the image, then runs OSD and rotates it if `args.auto_rotate` is set.
+    /// The OSD run counts towards the timeout of the command, which keeps the prepared
+    /// image alive and reports the rotation. In-memory images are piped to `stdin`.
+    fn create_image_command<O: AsRef<OsStr>>(
+        &self,
+        image: &Image,
+        output: O,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        let started = Instant::now();
+        let upright = self.prepare(image, args)?;
+        let image_libraries = self.image_libraries();
+        self.upright_image_command(image, upright, output, args, started, &image_libraries)
+    }
+
+    /// Applies the `Preprocess` attached to the image and `auto_rotate`,
+    /// `Upright::image` is the image to pass to tesseract instead, if any.
+    pub(crate) fn prepare(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        let preprocessed = self.with_resolved(image, Image::preprocessed)?;
+        let upright = self.auto_rotate(preprocessed.as_ref().unwrap_or(image), args)?;
+
+        Ok(Upright {
+            image: upright.image.or(preprocessed),
+            ..upright
+        })
+    }
+
+    /// Creates the command for an image prepared by `prepare` or `prepare_async`.
+    pub(crate) fn upright_image_command<O: AsRef<OsStr>>(
+        &self,
+        image: &Image,
+        upright: Upright,
+        output: O,
+        args: &Args,
+        started: Instant,
+        image_libraries: &[String],
+    ) -> TessResult<TesseractCommand> {
+        let input = upright.image.as_ref().unwrap_or(image);
+        self.check_image_format(input, image_libraries)?;
+        let mut command = match input.stdin_bytes() {
+            Some(bytes) => {
+                let mut command = self.create_tesseract_command_for_input("stdin", output, args)?;
+                command.stdin = Some(bytes.to_vec());
+                command
+            }
+            None => {
+                self.create_tesseract_command_for_input(input.get_image_path()?, output, args)?
+            }
+        };
+        command
+            .tempfiles
+            .extend(upright.image.and_then(Image::into_tempfile));
+        command.rotation = upright.rotation;
+        command.started = Some(started);
+
+        Ok(command)
+    }
+
+    /// Checks the format against the image libraries of the tesseract build.
+    fn check_image_format(&self, image: &Image, image_libraries: &[String]) -> TessResult<()> {
+        self.with_resolved(image, |x| x.check_format(image_libraries))
+    }
+
+    /// Calls `f` with the relative path of `image` resolved against `current_dir`,
+    /// to read the image in this process like the tesseract process does.
+    /// Resolves a relative output path against `current_dir`, where tesseract writes it.
+    pub(crate) fn resolve_path(&self, path: &Path) -> PathBuf {
+        match &self.current_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.to_path_buf(),
+        }
+    }
+
+    pub(crate) fn with_resolved<T>(&self, image: &Image, f: impl FnOnce(&Image) -> T) -> T {
+        let resolved = image.resolved(self.current_dir.as_deref());
+        f(resolved.as_ref().unwrap_or(image))
+    }
+
+    fn create_tesseract_command_for_input<I: AsRef<OsStr>, O: AsRef<OsStr>>(
+        &self,
+        input: I,
+        output: O,
+        args: &Args,
+    ) -> TessResult<TesseractCommand> {
+        // a tessdata directory in `args` is rendered together with the other options
+        let mut command = match args.tessdata_dir {
+            Some(_) => TesseractCommand::from(self.command_with_tessdata_dir(None)),
+            None => TesseractCommand::from(self.command()),
+        };
+        command.arg(input).arg(output);
+
+        let mut tempfiles = Vec::new();
+        let command_args = args.render_command_args(|list| match list {
+            WordList::Path(path) => Ok(path.clone()),
+            WordList::Entries(entries) => {
+                let tempfile = WordList::to_tempfile(entries)?;
+                let path = tempfile.path().to_path_buf();
+                tempfiles.push(tempfile);
+                Ok(path)
+            }
+        })?;
+        command.args(command_args);
+        command.tempfiles = tempfiles;
+
+        Ok(command)
+    }
+
+    pub(crate) fn run_tesseract_command<C: Into<TesseractCommand>>(
+        &self,
+        command: C,
+    ) -> TessResult<String> {
+        let output = self.run_tesseract_command_bytes(command)?;
+        Ok(lossy_string(output))
+    }
+
+    /// Runs the command and returns stdout unchanged, e.g. for binary PDF output.
+    pub(crate) fn run_tesseract_command_bytes<C: Into<TesseractCommand>>(
+        &self,
+        command: C,
+    ) -> TessResult<Vec<u8>> {
+        // the tempfiles have to outlive the tesseract process
+        let TesseractCommand {
+            mut command,
+            tempfiles: _tempfiles,
+            stdin,
+            started,
+            ..
+        } = command.into();
+        prepare_command(&mut command);
+        if stdin.is_some() {
+            command.stdin(Stdio::piped());
+        }
+
+        if self.is_cancelled() {
+            return Err(TessError::Cancelled(String::new()));
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|_| TessError::TesseractNotFoundError)?;
+
+        if let (Some(mut pipe), Some(bytes)) = (child.stdin.take(), stdin) {
+            // write errors surface as exit status of tesseract, dropping the pipe closes stdin
+            thread::spawn(move || {
+                let _ = pipe.write_all(&bytes);
+            });
+        }
+
+        // read both pipes in the background so that the process cannot block on a full pipe
+        // and the captured stderr is still available if the process has to be killed
+        let stdout = PipeReader::spawn(child.stdout.take());
+        let stderr = PipeReader::spawn(child.stderr.take());
+
+        match self.wait(&mut child, started.unwrap_or_else(Instant::now))? {
+            Wait::Exited(status) => {
+                let err = lossy_string(stderr.finish());
+
+                check_exit_status(status, stdout.finish(), err)
+            }
+            Wait::TimedOut(timeout) => Err(TessError::Timeout(timeout, stderr.captured())),
+            Wait::Cancelled => Err(TessError::Cancelled(stderr.captured())),
+        }
+    }
+
+    fn wait(&self, child: &mut Child, start: Instant) -> TessResult<Wait> {
+        if self.timeout.is_none() && self.cancellation.is_none() {
+            let status = child
+                .wait()
+                .map_err(|_| TessError::TesseractNotFoundError)?;
+            return Ok(Wait::Exited(status));
+        }
+
+        loop {
+            if let Some(status) = child
+                .try_wait()
+                .map_err(|_| TessError::TesseractNotFoundError)?
+            {
+                return Ok(Wait::Exited(status));
+            }
+
+            let reason = match self.timeout {
+                Some(timeout) if start.elapsed() >= timeout => Some(Wait::TimedOut(timeout)),
+                _ if self.is_cancelled() => Some(Wait::Cancelled),
+                _ => None,
+            };
+
+            if let Some(reason) = reason {
+                // the process may have exited in the meantime, so errors are ignored here
+                let _ = child.kill();
+                let _ = child.wait();
+                return Ok(reason);
+            }
+
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+}
+
+/// A tesseract `Command` together with the tempfiles it refers to
+/// and the image data to write to `stdin`.
+///
+/// The tempfiles are deleted when the `TesseractCommand` is dropped after the run.
+#[derive(Debug)]
+pub(crate) struct TesseractCommand {
+    pub(crate) command: Command,
+    pub(crate) tempfiles: Vec<tempfile::NamedTempFile>,
+    pub(crate) stdin: Option<Vec<u8>>,
+    /// start of the run including preceding steps such as OSD for `auto_rotate`,
+    /// the timeout of the engine counts from here instead of the spawn of the process
+    pub(crate) started: Option<Instant>,
+    /// rotation applied by `auto_rotate` (single images only)
+    pub(crate) rotation: Rotation,
+}
+
+impl From<Command> for TesseractCommand {
+    fn from(command: Command) -> Self {
+        TesseractCommand {
+            command,
+            tempfiles: Vec::new(),
+            stdin: None,
+            started: None,
+            rotation: Rotation::default(),
+        }
+    }
+}
+
+impl Deref for TesseractCommand {
+    type Target = Command;
+
+    fn deref(&self) -> &Self::Target {
+        &self.command
+    }
+}
+
+impl DerefMut for TesseractCommand {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.command
+    }
+}
+
+enum Wait {
+    Exited(ExitStatus),
+    TimedOut(Duration),
+    Cancelled,
+}
+
+struct PipeReader {
+    buffer: Arc<Mutex<Vec<u8>>>,
+    handle: thread::JoinHandle<()>,
+}
+
+impl PipeReader {
+    fn spawn<R: Read + Send + 'static>(pipe: Option<R>) -> Self {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let shared = Arc::clone(&buffer);
+        let handle = thread::spawn(move || {
+            let Some(mut pipe) = pipe else { return };
+            let mut chunk = [0; 4096];
+            while let Ok(n @ 1..) = pipe.read(&mut chunk) {
+                shared.lock().unwrap().extend_from_slice(&chunk[..n]);
+            }
+        });
+
+        PipeReader { buffer, handle }
+    }
+
+    /// Waits until the pipe is closed and returns everything that was read.
+    fn finish(self) -> Vec<u8> {
+        let _ = self.handle.join();
+        std::mem::take(&mut *self.buffer.lock().unwrap())
+    }
+
+    /// Returns what was read so far without waiting for the pipe to be closed,
+    /// which may never happen if the killed process left children behind.
+    fn captured(&self) -> String {
+        String::from_utf8_lossy(&self.buffer.lock().unwrap()).into_owned()
+    }
+}
+
+/// Handle to abort running tesseract processes from another thread.
+///
+/// Clones share the same state, so a token can be attached to a `TesseractEngine`
+/// and cancelled from anywhere else.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    /// wakes up async runs waiting for the cancellation
+    #[cfg(feature = "async")]
+    notify: tokio::sync::Notify,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        #[cfg(feature = "async")]
+        self.0.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Completes once the token is cancelled.
+    #[cfg(feature = "async")]
+    pub async fn cancelled(&self) {
+        // registered before the check, so a concurrent `cancel` cannot be missed
+        let notified = self.0.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+impl PartialEq for CancellationToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
 }
 
 pub fn get_tesseract_langs() -> TessResult<Vec<String>> {
-    let mut command = get_tesseract_command();
-    command.arg("--list-langs");
+    TesseractEngine::default().get_tesseract_langs()
+}
+
+pub fn get_tesseract_langs_for(args: &Args) -> TessResult<Vec<String>> {
+    TesseractEngine::default().get_tesseract_langs_for(args)
+}
+
+pub(crate) fn parse_langs(output: &str) -> Vec<String> {
+    output.lines().skip(1).map(|x| x.into()).collect()
+}
 
-    let output = run_tesseract_command(&mut command)?;
-    let langs = output.lines().skip(1).map(|x| x.into()).collect();
-    Ok(langs)
+/// Writes the list file tesseract reads instead of a single image, one image path per line.
+pub(crate) fn image_list_tempfile(paths: &[&str]) -> TessResult<tempfile::NamedTempFile> {
+    let mut tempfile = tempfile::Builder::new()
+        .prefix("rusty-tesseract")
+        .suffix(".txt")
+        .tempfile()
+        .map_err(|e| TessError::TempfileError(e.to_string()))?;
+    for path in paths {
+        writeln!(tempfile, "{}", path).map_err(|e| TessError::TempfileError(e.to_string()))?;
+    }
+
+    Ok(tempfile)
 }
 
-pub(crate) fn run_tesseract_command(command: &mut Command) -> TessResult<String> {
+pub(crate) fn prepare_command(command: &mut Command) {
     if cfg!(debug_assertions) {
         show_command(command);
     }
@@ -44,26 +567,25 @@ pub(crate) fn run_tesseract_command(command: &mut Command) -> TessResult<String>
     #[cfg(target_os = "windows")]
     command.creation_flags(CREATE_NO_WINDOW);
 
-    let child = command
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .spawn()
-        .map_err(|_| TessError::TesseractNotFoundError)?;
-
-    let output = child
-        .wait_with_output()
-        .map_err(|_| TessError::TesseractNotFoundError)?;
-
-    let out = String::from_utf8(output.stdout).unwrap();
-    let err = String::from_utf8(output.stderr).unwrap();
-    let status = output.status;
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+}
 
+pub(crate) fn check_exit_status(
+    status: ExitStatus,
+    out: Vec<u8>,
+    err: String,
+) -> TessResult<Vec<u8>> {
     match status.code() {
         Some(0) => Ok(out),
         _ => Err(TessError::CommandExitStatusError(status.to_string(), err)),
     }
 }
 
+/// Tesseract writes bytes as found in the image or its file names, which need not be valid UTF-8.
+pub(crate) fn lossy_string(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
 fn show_command(command: &Command) {
     let params: Vec<String> = command
         .get_args()
@@ -79,47 +601,290 @@ fn show_command(command: &Command) {
 }
 
 pub fn image_to_string(image: &Image, args: &Args) -> TessResult<String> {
-    let mut command = create_tesseract_command(image, args)?;
-    let output = run_tesseract_command(&mut command)?;
-
-    Ok(output)
+    TesseractEngine::default().image_to_string(image, args)
 }
 
-pub(crate) fn create_tesseract_command(image: &Image, args: &Args) -> TessResult<Command> {
-    let mut command = get_tesseract_command();
-    command
-        .arg(image.get_image_path()?)
-        .arg("stdout")
-        .arg("-l")
-        .arg(args.lang.clone());
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::ffi::OsStr;
+    use std::path::Path;
+    use std::process::Command;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_get_tesseract_langs() {
+        let langs = get_tesseract_langs().unwrap();
 
-    if let Some(dpi) = args.dpi {
-        command.arg("--dpi").arg(dpi.to_string());
+        assert!(langs.contains(&"eng".into()));
     }
 
-    if let Some(psm) = args.psm {
-        command.arg("--psm").arg(psm.to_string());
+    #[test]
+    fn test_engine_command() {
+        let engine = TesseractEngine {
+            envs: [("OMP_THREAD_LIMIT".to_string(), "1".to_string())].into(),
+            current_dir: Some("/tmp".into()),
+            tessdata_dir: Some("/opt/tessdata".into()),
+            ..TesseractEngine::new("/opt/tesseract/bin/tesseract")
+        };
+
+        let command = engine.command();
+        assert_eq!(command.get_program(), "/opt/tesseract/bin/tesseract");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec!["--tessdata-dir", "/opt/tessdata"]
+        );
+        assert_eq!(
+            command.get_envs().collect::<Vec<_>>(),
+            vec![(OsStr::new("OMP_THREAD_LIMIT"), Some(OsStr::new("1")))]
+        );
+        assert_eq!(command.get_current_dir(), Some(Path::new("/tmp")));
     }
 
-    if let Some(oem) = args.oem {
-        command.arg("--oem").arg(oem.to_string());
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout() {
+        let engine = TesseractEngine {
+            timeout: Some(Duration::from_millis(50)),
+            ..TesseractEngine::default()
+        };
+        let mut command = Command::new("sh");
+        command.arg("-c").arg("echo started >&2; sleep 5");
+
+        let start = Instant::now();
+        let result = engine.run_tesseract_command(command);
+
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            result,
+            Err(TessError::Timeout(
+                Duration::from_millis(50),
+                "started\n".into()
+            ))
+        );
     }
 
-    for parameter in args.get_config_variable_args() {
-        command.arg("-c").arg(parameter);
+    #[cfg(unix)]
+    #[test]
+    fn test_timeout_counts_from_start_of_run() {
+        let engine = TesseractEngine {
+            timeout: Some(Duration::from_millis(500)),
+            ..TesseractEngine::default()
+        };
+        let mut command = TesseractCommand::from(Command::new("sleep"));
+        command.arg("5");
+        command.started = Some(Instant::now() - Duration::from_secs(1));
+
+        let start = Instant::now();
+        let result = engine.run_tesseract_command(command);
+
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            result,
+            Err(TessError::Timeout(
+                Duration::from_millis(500),
+                String::new()
+            ))
+        );
     }
 
-    Ok(command)
-}
+    #[cfg(unix)]
+    #[test]
+    fn test_auto_rotate_step() {
+        use std::os::unix::fs::PermissionsExt;
 
-#[cfg(test)]
-mod tests {
-    use crate::*;
+        let dir = tempfile::tempdir().unwrap();
+        let executable = dir.path().join("tesseract");
+        std::fs::write(
+            &executable,
+            "#!/bin/sh\nprintf 'Page number: 0\\nOrientation in degrees: 180\\nRotate: 180\\n\\
+                Orientation confidence: 9.0\\nScript: Latin\\nScript confidence: 2.0\\n'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let engine = TesseractEngine::new(&executable);
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            auto_rotate: true,
+            ..Args::default()
+        };
+
+        let command = engine.create_tesseract_command(&img, &args).unwrap();
+
+        assert_eq!(command.rotation.degrees, 180);
+        assert!(command.started.is_some());
+        // the rotated image is piped to stdin instead of being written to a tempfile
+        assert_eq!(command.get_args().next(), Some(OsStr::new("stdin")));
+        assert!(command.stdin.as_ref().unwrap().starts_with(b"\x89PNG"));
+        assert!(command.tempfiles.is_empty());
+    }
 
+    #[cfg(unix)]
     #[test]
-    fn test_get_tesseract_langs() {
-        let langs = get_tesseract_langs().unwrap();
+    fn test_cancellation() {
+        let token = CancellationToken::new();
+        let engine = TesseractEngine {
+            cancellation: Some(token.clone()),
+            ..TesseractEngine::default()
+        };
+        let mut command = Command::new("sleep");
+        command.arg("5");
 
-        assert!(langs.contains(&"eng".into()));
+        let handle = thread::spawn(move || engine.run_tesseract_command(command));
+        thread::sleep(Duration::from_millis(50));
+        token.cancel();
+
+        assert_eq!(
+            handle.join().unwrap(),
+            Err(TessError::Cancelled(String::new()))
+        );
+    }
+
+    #[test]
+    fn test_user_words_tempfile() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            user_words: Some(WordList::Entries(vec!["LOREM".into(), "IPSUM".into()])),
+            user_patterns: Some(WordList::Path("patterns.txt".into())),
+            ..Args::default()
+        };
+
+        let command = TesseractEngine::default()
+            .create_tesseract_command(&img, &args)
+            .unwrap();
+        let command_args = command.get_args().collect::<Vec<_>>();
+        let position = command_args
+            .iter()
+            .position(|&x| x == "--user-words")
+            .unwrap();
+        let path = Path::new(command_args[position + 1]).to_path_buf();
+
+        assert!(command_args
+            .windows(2)
+            .any(|x| x == ["--user-patterns", "patterns.txt"]));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "LOREM\nIPSUM\n");
+
+        drop(command);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_in_memory_image_stdin() {
+        let bytes = std::fs::read("img/string.png").unwrap();
+        let img = Image::from_bytes(bytes.clone()).unwrap();
+
+        let command = TesseractEngine::default()
+            .create_tesseract_command(&img, &Args::default())
+            .unwrap();
+
+        assert_eq!(command.get_args().next(), Some(OsStr::new("stdin")));
+        assert_eq!(command.stdin, Some(bytes));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_stdin() {
+        let mut command = TesseractCommand::from(Command::new("cat"));
+        command.stdin = Some(b"LOREM IPSUM".to_vec());
+
+        let result = TesseractEngine::default().run_tesseract_command(command);
+
+        assert_eq!(result, Ok("LOREM IPSUM".into()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_invalid_utf8_output() {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg("printf 'A\\377'; printf 'B\\377' >&2; exit 1");
+
+        let result = TesseractEngine::default().run_tesseract_command(command);
+
+        assert_eq!(
+            result,
+            Err(TessError::CommandExitStatusError(
+                "exit status: 1".into(),
+                "B\u{FFFD}".into()
+            ))
+        );
+
+        let mut command = Command::new("printf");
+        command.arg("A\\377");
+        let result = TesseractEngine::default().run_tesseract_command(command);
+        assert_eq!(result, Ok("A\u{FFFD}".into()));
+    }
+
+    #[test]
+    fn test_config_files_and_tessdata_dir() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let engine = TesseractEngine {
+            tessdata_dir: Some("/usr/share/tessdata".into()),
+            ..TesseractEngine::default()
+        };
+        let args = Args {
+            config_files: vec!["digits".into(), "/opt/configs/myconfig".into()],
+            tessdata_dir: Some("/opt/tessdata".into()),
+            dpi: None,
+            psm: None,
+            oem: None,
+            ..Args::default()
+        };
+
+        let mut command = engine.create_tesseract_command(&img, &args).unwrap();
+        command.arg("tsv");
+
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            vec![
+                "img/string.png",
+                "stdout",
+                "-l",
+                "eng",
+                "--tessdata-dir",
+                "/opt/tessdata",
+                "digits",
+                "/opt/configs/myconfig",
+                "tsv"
+            ]
+        );
+        assert_eq!(
+            engine.command().get_args().collect::<Vec<_>>(),
+            vec!["--tessdata-dir", "/usr/share/tessdata"]
+        );
+    }
+
+    #[test]
+    fn test_relative_path_resolved_against_current_dir() {
+        let img = Image::from_path("string.png").unwrap();
+        let engine = TesseractEngine {
+            current_dir: Some("img".into()),
+            ..TesseractEngine::default()
+        };
+
+        let command = engine
+            .create_tesseract_command(&img, &Args::default())
+            .unwrap();
+        assert_eq!(command.get_args().next(), Some(OsStr::new("string.png")));
+        assert_eq!(engine.with_resolved(&img, Image::page_count), Ok(1));
+
+        assert_eq!(
+            TesseractEngine::default()
+                .create_tesseract_command(&img, &Args::default())
+                .unwrap_err(),
+            TessError::ImageNotFoundError
+        );
+    }
+
+    #[test]
+    fn test_engine_not_found() {
+        let engine = TesseractEngine::new("./does-not-exist/tesseract");
+
+        assert_eq!(
+            engine.get_tesseract_version(),
+            Err(TessError::TesseractNotFoundError)
+        );
     }
 }
@@ -0,0 +1,448 @@
+use super::{preprocess::otsu_threshold, *};
+use image::{
+    imageops::{self, FilterType},
+    DynamicImage, GenericImageView, ImageBuffer, Luma, Pixel, Rgba,
+};
+
+/// Larger images are scaled down to estimate the skew, the angle does not depend on the resolution.
+const MAX_ESTIMATE_SIZE: u32 = 1000;
+
+/// Upper limit of `max_angle`, larger skews are rather rotated pages, see `image_orientation`.
+pub const MAX_DESKEW_ANGLE: f32 = 45.0;
+
+/// Image straightened by `Image::deskew`, with the transformation to map
+/// results of the deskewed image back to the original image.
+#[derive(Debug)]
+pub struct Deskewed {
+    pub image: Image,
+    /// detected skew in degrees, positive if the text lines rise to the right;
+    /// the image was rotated clockwise by this angle
+    pub angle: f32,
+    original_size: (u32, u32),
+    size: (u32, u32),
+}
+
+impl Deskewed {
+    /// Maps a point of the deskewed image to the original image, both with the origin at the top left.
+    pub fn to_original(&self, x: f32, y: f32) -> (f32, f32) {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let (dx, dy) = (x - self.size.0 as f32 / 2.0, y - self.size.1 as f32 / 2.0);
+
+        (
+            dx * cos + dy * sin + self.original_size.0 as f32 / 2.0,
+            -dx * sin + dy * cos + self.original_size.1 as f32 / 2.0,
+        )
+    }
+
+    /// Replaces the bounding box with the one enclosing the rotated box in the original image.
+    pub fn data_to_original(&self, data: &Data) -> Data {
+        let mut data = data.clone();
+        data.set_rect(&self.rect_to_original(&data.rect()));
+        data
+    }
+
+    /// Like `data_to_original`, with the origin of the box coordinates at the bottom left.
+    pub fn box_to_original(&self, x: &Box) -> Box {
+        let rect = self.rect_to_original(&x.to_rect(self.size.1 as i32));
+        let mut x = x.clone();
+        x.set_rect(&rect, self.original_size.1 as i32);
+        x
+    }
+
+    /// Maps every row of a `image_to_data` result of the deskewed image with `data_to_original`,
+    /// the raw `output` is rewritten as well.
+    pub fn data_output_to_original(&self, output: &DataOutput) -> TessResult<DataOutput> {
+        let mut output = output.clone();
+        output.map_data(|x| x.set_rect(&self.rect_to_original(&x.rect())))?;
+        Ok(output)
+    }
+
+    /// Like `data_output_to_original`, for a `image_to_boxes` result.
+    pub fn box_output_to_original(&self, output: &BoxOutput) -> BoxOutput {
+        let mut output = output.clone();
+        output.map_boxes(|x| *x = self.box_to_original(x));
+        output
+    }
+
+    /// Returns the rectangle enclosing the rotated rectangle in the original image.
+    pub fn rect_to_original(&self, rect: &Rect) -> Rect {
+        let (left, top, right, bottom) = (
+            rect.left as f32,
+            rect.top as f32,
+            rect.right() as f32,
+            rect.bottom() as f32,
+        );
+        let corners = [(left, top), (right, top), (left, bottom), (right, bottom)]
+            .map(|(x, y)| self.to_original(x, y));
+        let min = |f: fn(&(f32, f32)) -> f32| corners.iter().map(f).fold(f32::MAX, f32::min);
+        let max = |f: fn(&(f32, f32)) -> f32| corners.iter().map(f).fold(f32::MIN, f32::max);
+
+        Rect::from_corners(
+            min(|x| x.0).floor() as i32,
+            min(|x| x.1).floor() as i32,
+            max(|x| x.0).ceil() as i32,
+            max(|x| x.1).ceil() as i32,
+        )
+    }
+}
+
+impl Image {
+    /// Estimates the skew of the text lines within `max_angle` degrees and returns the
+    /// straightened image as a new PNG tempfile. The canvas grows to fit the rotated
+    /// image and is filled with white.
+    ///
+    /// `max_angle` is clamped to `MAX_DESKEW_ANGLE`, angles that are not finite
+    /// or not positive fail with `TessError::DeskewAngleError`.
+    pub fn deskew(&self, max_angle: f32) -> TessResult<Deskewed> {
+        check_max_angle(max_angle)?;
+        let image = self.decode()?;
+        let angle = estimate_skew(&image, max_angle);
+        let rotated = rotate(&image, angle);
+
+        Ok(Deskewed {
+            image: Image::from_dynamic_image(&rotated)?,
+            angle,
+            original_size: image.dimensions(),
+            size: rotated.dimensions(),
+        })
+    }
+}
+
+/// Returns the skew of the text lines in degrees, see `Deskewed::angle`,
+/// or 0 if `max_angle` is invalid, see `Image::deskew`.
+///
+/// Uses projection profiles: the dark pixels are projected onto the vertical axis
+/// for every candidate angle, and the angle with the sharpest profile, i.e. where
+/// text lines and the gaps between them are separated best, wins.
+pub fn estimate_skew(image: &DynamicImage, max_angle: f32) -> f32 {
+    let Ok(max_angle) = check_max_angle(max_angle) else {
+        return 0.0;
+    };
+
+    let mut gray = image.to_luma8();
+    let size = gray.width().max(gray.height());
+    if size > MAX_ESTIMATE_SIZE {
+        let scale = |x: u32| (x as u64 * MAX_ESTIMATE_SIZE as u64 / size as u64).max(1) as u32;
+        gray = imageops::resize(
+            &gray,
+            scale(gray.width()),
+            scale(gray.height()),
+            FilterType::Triangle,
+        );
+    }
+
+    let threshold = otsu_threshold(&gray);
+    let mut points = gray
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel.0[0] <= threshold)
+        .map(|(x, y, _)| (x as f32, y as f32))
+        .collect::<Vec<_>>();
+    // light text on a dark background
+    if points.len() > gray.pixels().len() / 2 {
+        points = gray
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel.0[0] > threshold)
+            .map(|(x, y, _)| (x as f32, y as f32))
+            .collect();
+    }
+    if points.is_empty() {
+        return 0.0;
+    }
+
+    let offset = gray.width() as f32;
+    let rows = (gray.height() + 2 * gray.width()) as usize + 1;
+    let coarse = best_angle(&points, offset, rows, 0.0, max_angle, 0.5);
+    best_angle(&points, offset, rows, coarse, 0.5, 0.05).clamp(-max_angle, max_angle)
+}
+
+/// Returns `max_angle` clamped to `MAX_DESKEW_ANGLE`.
+pub(crate) fn check_max_angle(max_angle: f32) -> TessResult<f32> {
+    if max_angle.is_finite() && max_angle > 0.0 {
+        Ok(max_angle.min(MAX_DESKEW_ANGLE))
+    } else {
+        Err(TessError::DeskewAngleError(max_angle.to_string()))
+    }
+}
+
+/// Searches `center - range..=center + range` in steps of `step` degrees.
+fn best_angle(
+    points: &[(f32, f32)],
+    offset: f32,
+    rows: usize,
+    center: f32,
+    range: f32,
+    step: f32,
+) -> f32 {
+    let steps = (range / step).round() as i32;
+    let mut profile = vec![0u64; rows];
+
+    (-steps..=steps)
+        .map(|i| center + i as f32 * step)
+        .map(|angle| {
+            let (sin, cos) = angle.to_radians().sin_cos();
+            profile.fill(0);
+            for (x, y) in points {
+                let row = (y * cos + x * sin + offset) as usize;
+                profile[row.min(rows - 1)] += 1;
+            }
+            (angle, profile.iter().map(|x| x * x).sum::<u64>())
+        })
+        // prefer the smaller correction if several angles are equally good
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.abs().total_cmp(&a.0.abs())))
+        .map_or(0.0, |(angle, _)| angle)
+}
+
+/// Rotates clockwise by `angle` degrees onto a white canvas that fits the whole image.
+pub(crate) fn rotate(image: &DynamicImage, angle: f32) -> DynamicImage {
+    if angle == 0.0 {
+        return image.clone();
+    }
+
+    match image {
+        DynamicImage::ImageLuma8(gray) => rotate_buffer(gray, angle, Luma([255])).into(),
+        _ => rotate_buffer(&image.to_rgba8(), angle, Rgba([255, 255, 255, 255])).into(),
+    }
+}
+
+fn rotate_buffer<P: Pixel<Subpixel = u8>>(
+    image: &ImageBuffer<P, Vec<u8>>,
+    angle: f32,
+    fill: P,
+) -> ImageBuffer<P, Vec<u8>> {
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let (width, height) = (image.width() as f32, image.height() as f32);
+    let rotated_width = (width * cos.abs() + height * sin.abs()).ceil();
+    let rotated_height = (width * sin.abs() + height * cos.abs()).ceil();
+
+    ImageBuffer::from_fn(rotated_width as u32, rotated_height as u32, |u, v| {
+        // inverse rotation of the pixel center, interpolate_bilinear expects pixel centers at integers
+        let (dx, dy) = (
+            u as f32 + 0.5 - rotated_width / 2.0,
+            v as f32 + 0.5 - rotated_height / 2.0,
+        );
+        let x = dx * cos + dy * sin + width / 2.0 - 0.5;
+        let y = -dx * sin + dy * cos + height / 2.0 - 0.5;
+
+        imageops::interpolate_bilinear(image, x, y).unwrap_or(fill)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        deskew::{check_max_angle, estimate_skew, rotate},
+        output_boxes::string_to_boxes,
+        output_data::string_to_data,
+        *,
+    };
+    use image::{DynamicImage, GenericImageView, GrayImage, Luma};
+
+    /// Rows of dark "words" on white.
+    fn text_lines() -> DynamicImage {
+        GrayImage::from_fn(400, 300, |x, y| {
+            let word = x % 60 < 45 && (20..380).contains(&x);
+            let line = y % 25 < 8 && (20..280).contains(&y);
+            Luma([if word && line { 0 } else { 255 }])
+        })
+        .into()
+    }
+
+    #[test]
+    fn test_estimate_skew() {
+        let image = text_lines();
+
+        assert_eq!(estimate_skew(&image, 5.0), 0.0);
+        for angle in [-3.0f32, 1.5, 4.0] {
+            // rotating counterclockwise makes the lines rise to the right
+            let skewed = rotate(&image, -angle);
+            let estimated = estimate_skew(&skewed, 5.0);
+            assert!(
+                (estimated - angle).abs() < 0.2,
+                "{} != {}",
+                estimated,
+                angle
+            );
+        }
+    }
+
+    #[test]
+    fn test_deskew() {
+        let skewed = Image::from_dynamic_image(&rotate(&text_lines(), -3.0)).unwrap();
+        let original_size = image::open(skewed.get_image_path().unwrap())
+            .unwrap()
+            .dimensions();
+
+        let result = skewed.deskew(5.0).unwrap();
+        assert!((result.angle - 3.0).abs() < 0.2);
+
+        let (width, height) = image::open(result.image.get_image_path().unwrap())
+            .unwrap()
+            .dimensions();
+        assert!(width > original_size.0 && height > original_size.1);
+        let (x, y) = result.to_original(width as f32 / 2.0, height as f32 / 2.0);
+        assert!((x - original_size.0 as f32 / 2.0).abs() < 0.01);
+        assert!((y - original_size.1 as f32 / 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_to_original() {
+        let img = Image::from_dynamic_image(&text_lines()).unwrap();
+        let result = img.deskew(5.0).unwrap();
+        assert_eq!(result.angle, 0.0);
+
+        let data = Data {
+            level: 5,
+            page_num: 1,
+            block_num: 1,
+            par_num: 1,
+            line_num: 1,
+            word_num: 1,
+            left: 20,
+            top: 25,
+            width: 45,
+            height: 8,
+            conf: 90.0,
+            text: "LOREM".into(),
+        };
+        assert_eq!(result.data_to_original(&data), data);
+
+        let x = Box {
+            symbol: "L".into(),
+            left: 20,
+            bottom: 267,
+            right: 65,
+            top: 275,
+            page: 0,
+        };
+        assert_eq!(result.box_to_original(&x), x);
+    }
+
+    #[test]
+    fn test_output_to_original() {
+        let original = text_lines();
+        let rotated = rotate(&original, 10.0);
+        let result = Deskewed {
+            image: Image::from_dynamic_image(&rotated).unwrap(),
+            angle: 10.0,
+            original_size: original.dimensions(),
+            size: rotated.dimensions(),
+        };
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            5\t1\t1\t1\t1\t1\t200\t150\t45\t8\t90\tLOREM\n";
+        let data = DataOutput {
+            output: tsv.into(),
+            data: string_to_data(tsv).unwrap(),
+        };
+        let boxes = "L 200 150 245 158 0\n";
+        let boxes = BoxOutput {
+            output: boxes.into(),
+            boxes: string_to_boxes(boxes).unwrap(),
+        };
+
+        let mapped = result.data_output_to_original(&data).unwrap();
+        assert_eq!(mapped.data, vec![result.data_to_original(&data.data[0])]);
+        assert_ne!(mapped.data, data.data);
+        assert_eq!(string_to_data(&mapped.output).unwrap(), mapped.data);
+
+        let mapped = result.box_output_to_original(&boxes);
+        assert_eq!(mapped.boxes, vec![result.box_to_original(&boxes.boxes[0])]);
+        assert_ne!(mapped.boxes, boxes.boxes);
+        assert_eq!(string_to_boxes(&mapped.output).unwrap(), mapped.boxes);
+    }
+
+    #[test]
+    fn test_max_angle_validation() {
+        assert_eq!(check_max_angle(5.0), Ok(5.0));
+        assert_eq!(check_max_angle(90.0), Ok(MAX_DESKEW_ANGLE));
+        for invalid in [0.0, -5.0, f32::NAN, f32::INFINITY] {
+            assert_eq!(
+                check_max_angle(invalid),
+                Err(TessError::DeskewAngleError(invalid.to_string()))
+            );
+        }
+
+        let img = Image::from_dynamic_image(&text_lines()).unwrap();
+        assert_eq!(
+            img.deskew(f32::NAN).unwrap_err(),
+            TessError::DeskewAngleError("NaN".into())
+        );
+        assert_eq!(estimate_skew(&text_lines(), f32::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn test_to_original_rotated() {
+        // a small dark square off the center, so the growth of the canvas matters
+        let original = GrayImage::from_fn(200, 100, |x, y| {
+            let inside = (150..156).contains(&x) && (20..26).contains(&y);
+            Luma([if inside { 0 } else { 255 }])
+        });
+        let square = Rect::new(150, 20, 6, 6);
+        let rotated = rotate(&original.into(), 10.0);
+        let result = Deskewed {
+            image: Image::from_dynamic_image(&rotated).unwrap(),
+            angle: 10.0,
+            original_size: (200, 100),
+            size: rotated.dimensions(),
+        };
+
+        let dark = rotated
+            .to_luma8()
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel.0[0] < 128)
+            .map(|(x, y, _)| Rect::new(x as i32, y as i32, 1, 1))
+            .reduce(|a, b| a.union(&b))
+            .unwrap();
+        let center = (
+            dark.left as f32 + dark.width as f32 / 2.0,
+            dark.top as f32 + dark.height as f32 / 2.0,
+        );
+        let (x, y) = result.to_original(center.0, center.1);
+        assert!(
+            (x - 153.0).abs() < 1.0 && (y - 23.0).abs() < 1.0,
+            "{} {}",
+            x,
+            y
+        );
+
+        // the enclosing box of the rotated box contains the square and grows by a few pixels
+        let data = Data {
+            level: 5,
+            page_num: 1,
+            block_num: 1,
+            par_num: 1,
+            line_num: 1,
+            word_num: 1,
+            left: dark.left,
+            top: dark.top,
+            width: dark.width,
+            height: dark.height,
+            conf: 90.0,
+            text: "X".into(),
+        };
+        let mapped = result.data_to_original(&data).rect();
+        assert!(mapped.contains(&square), "{:?}", mapped);
+        assert!(mapped.iou(&square) > 0.4, "{:?}", mapped);
+
+        let (left, bottom, right, top) = dark.to_bottom_left(result.size.1 as i32);
+        let x = Box {
+            symbol: "X".into(),
+            left,
+            bottom,
+            right,
+            top,
+            page: 0,
+        };
+        assert_eq!(result.box_to_original(&x).to_rect(100), mapped);
+    }
+
+    #[test]
+    fn test_image_to_string_deskewed() {
+        let image = image::open("img/string.png").unwrap();
+        let skewed = Image::from_dynamic_image(&rotate(&image, -3.0)).unwrap();
+
+        let deskewed = skewed.deskew(5.0).unwrap();
+
+        let result = image_to_string(&deskewed.image, &Args::default()).unwrap();
+        assert_eq!(result.trim(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+}
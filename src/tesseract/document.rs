@@ -0,0 +1,365 @@
+use super::*;
+
+/// Hierarchical view of `DataOutput`: `Page` → `Block` → `Paragraph` → `Line` → `Word`.
+///
+/// Bounding boxes are taken from the rows of the corresponding level, confidences of the
+/// upper levels are the mean of their word confidences.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Document {
+    pub pages: Vec<Page>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Page {
+    pub page_num: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Block {
+    pub block_num: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub paragraphs: Vec<Paragraph>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Paragraph {
+    pub par_num: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub lines: Vec<Line>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Line {
+    pub line_num: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub words: Vec<Word>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Word {
+    pub word_num: i32,
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+    pub conf: f32,
+    pub text: String,
+}
+
+impl Document {
+    pub fn from_data(data: &[Data]) -> Self {
+        let mut document = Document::default();
+
+        for row in data {
+            let page = last_or_insert(
+                &mut document.pages,
+                row.page_num,
+                |x| x.page_num,
+                |num| Page {
+                    page_num: num,
+                    ..Default::default()
+                },
+            );
+            if row.level == 1 {
+                (page.left, page.top, page.width, page.height) = bounding_box(row);
+                continue;
+            }
+
+            let block = last_or_insert(
+                &mut page.blocks,
+                row.block_num,
+                |x| x.block_num,
+                |num| Block {
+                    block_num: num,
+                    ..Default::default()
+                },
+            );
+            if row.level == 2 {
+                (block.left, block.top, block.width, block.height) = bounding_box(row);
+                continue;
+            }
+
+            let paragraph = last_or_insert(
+                &mut block.paragraphs,
+                row.par_num,
+                |x| x.par_num,
+                |num| Paragraph {
+                    par_num: num,
+                    ..Default::default()
+                },
+            );
+            if row.level == 3 {
+                (
+                    paragraph.left,
+                    paragraph.top,
+                    paragraph.width,
+                    paragraph.height,
+                ) = bounding_box(row);
+                continue;
+            }
+
+            let line = last_or_insert(
+                &mut paragraph.lines,
+                row.line_num,
+                |x| x.line_num,
+                |num| Line {
+                    line_num: num,
+                    ..Default::default()
+                },
+            );
+            if row.level == 4 {
+                (line.left, line.top, line.width, line.height) = bounding_box(row);
+                continue;
+            }
+
+            line.words.push(Word {
+                word_num: row.word_num,
+                left: row.left,
+                top: row.top,
+                width: row.width,
+                height: row.height,
+                conf: row.conf,
+                text: row.text.clone(),
+            });
+        }
+
+        document
+    }
+
+    /// Text of all pages, separated by a form feed like tesseract's text output.
+    pub fn text(&self) -> String {
+        join(self.pages.iter().map(Page::text), "\x0c")
+    }
+
+    pub fn conf(&self) -> Option<f32> {
+        mean_conf(self.words())
+    }
+
+    pub fn pages(&self) -> impl Iterator<Item = &Page> {
+        self.pages.iter()
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &Block> {
+        self.pages().flat_map(Page::blocks)
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = &Paragraph> {
+        self.blocks().flat_map(Block::paragraphs)
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.paragraphs().flat_map(Paragraph::lines)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.lines().flat_map(Line::words)
+    }
+}
+
+impl Page {
+    /// Text of all blocks, separated by an empty line.
+    pub fn text(&self) -> String {
+        join(self.blocks.iter().map(Block::text), "\n\n")
+    }
+
+    pub fn conf(&self) -> Option<f32> {
+        mean_conf(self.words())
+    }
+
+    pub fn blocks(&self) -> impl Iterator<Item = &Block> {
+        self.blocks.iter()
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = &Paragraph> {
+        self.blocks().flat_map(Block::paragraphs)
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.paragraphs().flat_map(Paragraph::lines)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.lines().flat_map(Line::words)
+    }
+}
+
+impl Block {
+    /// Text of all paragraphs, separated by an empty line.
+    pub fn text(&self) -> String {
+        join(self.paragraphs.iter().map(Paragraph::text), "\n\n")
+    }
+
+    pub fn conf(&self) -> Option<f32> {
+        mean_conf(self.words())
+    }
+
+    pub fn paragraphs(&self) -> impl Iterator<Item = &Paragraph> {
+        self.paragraphs.iter()
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.paragraphs().flat_map(Paragraph::lines)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.lines().flat_map(Line::words)
+    }
+}
+
+impl Paragraph {
+    /// Text of all lines, separated by a line break.
+    pub fn text(&self) -> String {
+        join(self.lines.iter().map(Line::text), "\n")
+    }
+
+    pub fn conf(&self) -> Option<f32> {
+        mean_conf(self.words())
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &Line> {
+        self.lines.iter()
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.lines().flat_map(Line::words)
+    }
+}
+
+impl Line {
+    /// Text of all words, separated by a space.
+    pub fn text(&self) -> String {
+        join(self.words.iter().map(|x| x.text.clone()), " ")
+    }
+
+    pub fn conf(&self) -> Option<f32> {
+        mean_conf(self.words())
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &Word> {
+        self.words.iter()
+    }
+}
+
+impl Word {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl From<&DataOutput> for Document {
+    fn from(value: &DataOutput) -> Self {
+        Document::from_data(&value.data)
+    }
+}
+
+impl DataOutput {
+    pub fn document(&self) -> Document {
+        Document::from(self)
+    }
+}
+
+/// Returns the last node if it has the given number, otherwise appends a new one.
+fn last_or_insert<T>(
+    nodes: &mut Vec<T>,
+    num: i32,
+    num_of: impl Fn(&T) -> i32,
+    new: impl FnOnce(i32) -> T,
+) -> &mut T {
+    if nodes.last().map(&num_of) != Some(num) {
+        nodes.push(new(num));
+    }
+    nodes.last_mut().unwrap()
+}
+
+fn bounding_box(row: &Data) -> (i32, i32, i32, i32) {
+    (row.left, row.top, row.width, row.height)
+}
+
+fn join(parts: impl Iterator<Item = String>, separator: &str) -> String {
+    parts
+        .filter(|x| !x.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Mean of all word confidences, ignoring words without one (`-1`).
+fn mean_conf<'a>(words: impl Iterator<Item = &'a Word>) -> Option<f32> {
+    let confs = words
+        .map(|x| x.conf)
+        .filter(|&x| x >= 0.0)
+        .collect::<Vec<_>>();
+    if confs.is_empty() {
+        None
+    } else {
+        Some(confs.iter().sum::<f32>() / confs.len() as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_data::string_to_data, *};
+
+    fn document() -> Document {
+        let data = string_to_data(
+            "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            1\t1\t0\t0\t0\t0\t0\t0\t696\t189\t-1\t\n\
+            2\t1\t1\t0\t0\t0\t18\t29\t653\t135\t-1\t\n\
+            3\t1\t1\t1\t0\t0\t18\t29\t653\t135\t-1\t\n\
+            4\t1\t1\t1\t1\t0\t18\t29\t304\t35\t-1\t\n\
+            5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t90\tLOREM\n\
+            5\t1\t1\t1\t1\t2\t181\t29\t123\t35\t80\tIPSUM\n\
+            4\t1\t1\t1\t2\t0\t18\t129\t304\t35\t-1\t\n\
+            5\t1\t1\t1\t2\t1\t18\t129\t153\t35\t70\tDOLOR\n\
+            3\t1\t1\t2\t0\t0\t18\t160\t100\t35\t-1\t\n\
+            4\t1\t1\t2\t1\t0\t18\t160\t100\t35\t-1\t\n\
+            5\t1\t1\t2\t1\t1\t18\t160\t100\t35\t60\tSIT",
+        )
+        .unwrap();
+        Document::from_data(&data)
+    }
+
+    #[test]
+    fn test_document_structure() {
+        let document = document();
+
+        assert_eq!(document.pages.len(), 1);
+        assert_eq!(document.blocks().count(), 1);
+        assert_eq!(document.paragraphs().count(), 2);
+        assert_eq!(document.lines().count(), 3);
+        assert_eq!(
+            document.words().map(Word::text).collect::<Vec<_>>(),
+            vec!["LOREM", "IPSUM", "DOLOR", "SIT"]
+        );
+
+        let page = &document.pages[0];
+        assert_eq!((page.width, page.height), (696, 189));
+        let line = document.lines().nth(1).unwrap();
+        assert_eq!((line.line_num, line.top, line.width), (2, 129, 304));
+    }
+
+    #[test]
+    fn test_document_text_and_conf() {
+        let document = document();
+        let paragraph = document.paragraphs().next().unwrap();
+
+        assert_eq!(paragraph.lines[0].text(), "LOREM IPSUM");
+        assert_eq!(paragraph.text(), "LOREM IPSUM\nDOLOR");
+        assert_eq!(document.text(), "LOREM IPSUM\nDOLOR\n\nSIT");
+        assert_eq!(paragraph.lines[0].conf(), Some(85.0));
+        assert_eq!(document.conf(), Some(75.0));
+    }
+}
@@ -1,6 +1,9 @@
+use std::time::Duration;
 use thiserror::Error;
 
-#[derive(Error, Debug, PartialEq)]
+use crate::InvalidArgument;
+
+#[derive(Clone, Error, Debug, PartialEq)]
 pub enum TessError {
     #[error("Tesseract not found. Please check installation path!")]
     TesseractNotFoundError,
@@ -8,11 +11,15 @@ pub enum TessError {
     #[error("Command ExitStatusError\n{0}")]
     CommandExitStatusError(String, String),
 
+    /// detected format from the content and declared format from the file extension
     #[error(
-        "Image format not within the list of allowed image formats:\n\
-        ['JPEG','JPG','PNG','PBM','PGM','PPM','TIFF','BMP','GIF','WEBP']"
+        "Image format {} (declared as {}) is not supported by the installed leptonica.\n\
+        Supported formats are BMP and PNM, and GIF, JPEG, JPEG 2000, PNG, TIFF and WebP\n\
+        if leptonica was built with the library listed by `tesseract --version`.",
+        or_unknown(.0),
+        or_unknown(.1)
     )]
-    ImageFormatError,
+    ImageFormatError(Option<String>, Option<String>),
 
     #[error("Please assign a valid image path.")]
     ImageNotFoundError,
@@ -25,6 +32,48 @@ pub enum TessError {
 
     #[error("Could not save dynamic image to tempfile.\n{0}")]
     DynamicImageError(String),
+
+    #[error("Could not decode image.\n{0}")]
+    ImageDecodeError(String),
+
+    #[error("Region outside of the image.\n{0}")]
+    RegionError(String),
+
+    #[error("Could not access output file.\n{0}")]
+    OutputFileError(String),
+
+    #[error("Invalid page segmentation mode '{0}'. Valid modes are 0-13.")]
+    PageSegModeError(String),
+
+    #[error("Invalid OCR engine mode '{0}'. Valid modes are 0-3.")]
+    OcrEngineModeError(String),
+
+    #[error("Invalid deskew angle '{0}'. Valid angles are finite and larger than 0.")]
+    DeskewAngleError(String),
+
+    #[error("Invalid arguments:\n{}", format_invalid_arguments(.0))]
+    InvalidArgsError(Vec<InvalidArgument>),
+
+    #[error("Tesseract version {0} is older than the required version {1}.")]
+    VersionError(String, String),
+
+    #[error("Tesseract did not finish within {0:?} and was killed.\n{1}")]
+    Timeout(Duration, String),
+
+    #[error("Tesseract was cancelled.\n{0}")]
+    Cancelled(String),
 }
 
 pub type TessResult<T> = Result<T, TessError>;
+
+fn format_invalid_arguments(invalid: &[InvalidArgument]) -> String {
+    invalid
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn or_unknown(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("unknown")
+}
@@ -1,95 +1,438 @@
-use image::DynamicImage;
+use image::{DynamicImage, ImageFormat};
 use std::{
     collections::HashMap,
+    convert::Infallible,
+    ffi::OsString,
     fmt::{self},
+    fs::File,
+    io::{Read, Write},
     path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
 };
 
-use crate::{TessError, TessResult};
+use crate::{Languages, OcrEngineMode, PageSegMode, Preprocess, TessError, TessResult};
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Args {
-    pub lang: String,
+    pub lang: Languages,
     pub config_variables: HashMap<String, String>,
     pub dpi: Option<i32>,
-    pub psm: Option<i32>,
-    pub oem: Option<i32>,
+    pub psm: Option<PageSegMode>,
+    pub oem: Option<OcrEngineMode>,
+    pub user_words: Option<WordList>,
+    pub user_patterns: Option<WordList>,
+    /// config files such as `digits`, `hocr` or a path to a custom config, appended after all options
+    pub config_files: Vec<PathBuf>,
+    /// directory containing the `.traineddata` files, overrides the one of the `TesseractEngine`
+    pub tessdata_dir: Option<PathBuf>,
+    /// detect the orientation with OSD first and rotate the image upright before recognition;
+    /// the OSD run counts towards the timeout of the engine, and the coordinates of the results
+    /// refer to the rotated image (see `CombinedOutput::rotation` and `image_upright`)
+    pub auto_rotate: bool,
 }
 
 impl Default for Args {
     fn default() -> Self {
         Args {
-            lang: "eng".into(),
+            lang: Languages::default(),
             config_variables: HashMap::new(),
             dpi: Some(150),
-            psm: Some(3),
-            oem: Some(3),
+            psm: Some(PageSegMode::Auto),
+            oem: Some(OcrEngineMode::Default),
+            user_words: None,
+            user_patterns: None,
+            config_files: Vec::new(),
+            tessdata_dir: None,
+            auto_rotate: false,
         }
     }
 }
 
 impl Args {
+    /// Sorted by name, so the generated command line does not change between runs.
     pub(crate) fn get_config_variable_args(&self) -> Vec<String> {
-        self.config_variables
-            .iter()
+        let mut config_variables = self.config_variables.iter().collect::<Vec<_>>();
+        config_variables.sort();
+        config_variables
+            .into_iter()
             .map(|(key, value)| format!("{}={}", key, value))
             .collect::<Vec<_>>()
     }
+
+    /// Renders the arguments passed to tesseract after the image and the output base,
+    /// e.g. `["-l", "eng", "--dpi", "150", "--psm", "3", "--oem", "3", "-c", "key=value"]`.
+    ///
+    /// The result is deterministic and can be used to log, hash or replay invocations.
+    /// In-memory word lists are written to a new tempfile for every run, so their path
+    /// is rendered as `<in-memory>`.
+    pub fn to_command_args(&self) -> Vec<String> {
+        self.render_command_args(|list| match list {
+            WordList::Path(path) => Ok::<_, Infallible>(path.clone()),
+            WordList::Entries(_) => Ok(PathBuf::from("<in-memory>")),
+        })
+        .unwrap_or_else(|e| match e {})
+        .into_iter()
+        .map(|x| x.to_string_lossy().into_owned())
+        .collect()
+    }
+
+    pub(crate) fn render_command_args<E>(
+        &self,
+        mut word_list_path: impl FnMut(&WordList) -> Result<PathBuf, E>,
+    ) -> Result<Vec<OsString>, E> {
+        let mut args: Vec<OsString> = Vec::new();
+
+        if !self.lang.is_empty() {
+            args.extend(["-l".into(), self.lang.to_string().into()]);
+        }
+
+        if let Some(tessdata_dir) = &self.tessdata_dir {
+            args.extend(["--tessdata-dir".into(), tessdata_dir.into()]);
+        }
+
+        if let Some(dpi) = self.dpi {
+            args.extend(["--dpi".into(), dpi.to_string().into()]);
+        }
+
+        if let Some(psm) = self.psm {
+            args.extend(["--psm".into(), psm.to_string().into()]);
+        }
+
+        if let Some(oem) = self.oem {
+            args.extend(["--oem".into(), oem.to_string().into()]);
+        }
+
+        if let Some(user_words) = &self.user_words {
+            args.extend(["--user-words".into(), word_list_path(user_words)?.into()]);
+        }
+
+        if let Some(user_patterns) = &self.user_patterns {
+            args.extend([
+                "--user-patterns".into(),
+                word_list_path(user_patterns)?.into(),
+            ]);
+        }
+
+        for parameter in self.get_config_variable_args() {
+            args.extend(["-c".into(), parameter.into()]);
+        }
+
+        // config files are positional and have to follow all options
+        args.extend(self.config_files.iter().map(|x| x.into()));
+
+        Ok(args)
+    }
+}
+
+/// Word or pattern list passed to tesseract via `--user-words` or `--user-patterns`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WordList {
+    /// file containing one entry per line
+    Path(PathBuf),
+    /// entries that are written to a tempfile for the duration of the tesseract run
+    Entries(Vec<String>),
+}
+
+impl WordList {
+    pub(crate) fn to_tempfile(entries: &[String]) -> TessResult<tempfile::NamedTempFile> {
+        let mut tempfile = tempfile::Builder::new()
+            .prefix("rusty-tesseract")
+            .suffix(".txt")
+            .tempfile()
+            .map_err(|e| TessError::TempfileError(e.to_string()))?;
+        for entry in entries {
+            writeln!(tempfile, "{}", entry).map_err(|e| TessError::TempfileError(e.to_string()))?;
+        }
+
+        Ok(tempfile)
+    }
+}
+
+impl From<PathBuf> for WordList {
+    fn from(value: PathBuf) -> Self {
+        WordList::Path(value)
+    }
+}
+
+impl From<&Path> for WordList {
+    fn from(value: &Path) -> Self {
+        WordList::Path(value.into())
+    }
+}
+
+impl From<Vec<String>> for WordList {
+    fn from(value: Vec<String>) -> Self {
+        WordList::Entries(value)
+    }
+}
+
+/// Formats of the `image` crate that leptonica can read, from a file as well as from `stdin`,
+/// with the image library it has to be built with (`None` if leptonica reads the format itself).
+const LEPTONICA_FORMATS: [(ImageFormat, Option<&str>); 7] = [
+    (ImageFormat::Png, Some("libpng")),
+    (ImageFormat::Jpeg, Some("libjpeg")),
+    (ImageFormat::Tiff, Some("libtiff")),
+    (ImageFormat::Bmp, None),
+    (ImageFormat::Pnm, None),
+    (ImageFormat::Gif, Some("libgif")),
+    (ImageFormat::WebP, Some("libwebp")),
+];
+
+/// JP2 container and raw J2K codestream signatures, JPEG 2000 is unknown to the `image` crate.
+const JPEG2000_SIGNATURES: [&[u8]; 2] = [b"\x00\x00\x00\x0cjP  \r\n\x87\n", b"\xff\x4f\xff\x51"];
+
+/// Image format detected from the magic bytes at the start of the content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DetectedFormat {
+    Image(ImageFormat),
+    Jpeg2000,
+}
+
+impl DetectedFormat {
+    fn detect(header: &[u8]) -> Option<Self> {
+        if JPEG2000_SIGNATURES.iter().any(|x| header.starts_with(x)) {
+            return Some(DetectedFormat::Jpeg2000);
+        }
+        image::guess_format(header).ok().map(DetectedFormat::Image)
+    }
+
+    fn from_extension(path: &Path) -> Option<Self> {
+        match path.extension()?.to_string_lossy().to_lowercase().as_str() {
+            "jp2" | "j2k" => Some(DetectedFormat::Jpeg2000),
+            extension => ImageFormat::from_extension(extension).map(DetectedFormat::Image),
+        }
+    }
+
+    /// Whether leptonica can read the format, given the `image_libraries` of `TesseractVersion`.
+    /// Without any libraries, every format leptonica can be built for is accepted.
+    fn is_supported(&self, image_libraries: &[String]) -> bool {
+        let library = match self {
+            DetectedFormat::Image(format) => {
+                match LEPTONICA_FORMATS.iter().find(|x| x.0 == *format) {
+                    Some((_, library)) => *library,
+                    None => return false,
+                }
+            }
+            DetectedFormat::Jpeg2000 => Some("libopenjp2"),
+        };
+
+        match library {
+            Some(library) if !image_libraries.is_empty() => {
+                image_libraries.iter().any(|x| x == library)
+            }
+            _ => true,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            DetectedFormat::Image(format) => format.extensions_str()[0],
+            DetectedFormat::Jpeg2000 => "jp2",
+        }
+    }
+}
+
+impl fmt::Display for DetectedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DetectedFormat::Image(ImageFormat::Png) => "PNG",
+            DetectedFormat::Image(ImageFormat::Jpeg) => "JPEG",
+            DetectedFormat::Image(ImageFormat::Tiff) => "TIFF",
+            DetectedFormat::Image(ImageFormat::Bmp) => "BMP",
+            DetectedFormat::Image(ImageFormat::Pnm) => "PNM",
+            DetectedFormat::Image(ImageFormat::Gif) => "GIF",
+            DetectedFormat::Image(ImageFormat::WebP) => "WebP",
+            DetectedFormat::Image(format) => {
+                return write!(f, "{}", format.extensions_str()[0].to_uppercase())
+            }
+            DetectedFormat::Jpeg2000 => "JPEG 2000",
+        };
+
+        write!(f, "{}", name)
+    }
 }
 
 #[derive(Debug)]
 pub struct Image {
     data: InputData,
+    /// applied when tesseract runs, see `Image::with_preprocess`
+    pub(crate) preprocess: Option<Preprocess>,
 }
 
 impl Image {
+    /// Detects the format from the content of the file and returns `TessError::ImageFormatError`
+    /// if leptonica cannot read it. Relative paths that do not exist in the working directory
+    /// may refer to the `current_dir` of the `TesseractEngine`, their format is taken from
+    /// the extension instead.
+    ///
+    /// Whether the leptonica of the installed tesseract was built with the library
+    /// for the format is checked again when tesseract runs.
     pub fn from_path<P: Into<PathBuf>>(path: P) -> TessResult<Self> {
         let path = path.into();
-        Self::check_image_format(&path)?;
-        Ok(Self {
-            data: InputData::Path(path),
-        })
+        let image = Self {
+            data: InputData::Path(path.clone()),
+            preprocess: None,
+        };
+
+        match image.check_format(&[]) {
+            Err(TessError::ImageNotFoundError) if path.is_relative() => {
+                let declared = path.extension().map(|x| x.to_string_lossy().to_uppercase());
+                match DetectedFormat::from_extension(&path) {
+                    Some(format) if format.is_supported(&[]) => Ok(image),
+                    detected => Err(TessError::ImageFormatError(
+                        detected.map(|x| x.to_string()),
+                        declared,
+                    )),
+                }
+            }
+            result => result.map(|_| image),
+        }
     }
 
-    fn check_image_format(path: &Path) -> TessResult<()> {
-        let binding = path
-            .extension()
-            .ok_or(TessError::ImageFormatError)?
-            .to_str()
-            .ok_or(TessError::ImageFormatError)?
-            .to_uppercase();
-        if matches!(
-            binding.as_str(),
-            "JPEG" | "JPG" | "PNG" | "PBM" | "PGM" | "PPM" | "TIFF" | "BMP" | "GIF" | "WEBP"
-        ) {
-            Ok(())
-        } else {
-            Err(TessError::ImageFormatError)
+    /// Returns the image with its relative path resolved against `current_dir`,
+    /// or `None` if the path can be used as is, e.g. to read it in this process.
+    pub(crate) fn resolved(&self, current_dir: Option<&Path>) -> Option<Image> {
+        match (&self.data, current_dir) {
+            (InputData::Path(path), Some(dir)) if path.is_relative() => Some(Image {
+                data: InputData::Path(dir.join(path)),
+                preprocess: self.preprocess.clone(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns an image with the same content that can be moved to another thread.
+    #[cfg(feature = "async")]
+    pub(crate) fn share(&self) -> Image {
+        let data = match &self.data {
+            InputData::Path(x) => InputData::Path(x.clone()),
+            InputData::Image(x) => InputData::Image(DynamicInput {
+                image: x.image.clone(),
+                tempfile: OnceLock::new(),
+            }),
+            InputData::Bytes(x) => InputData::Bytes(InMemoryImage {
+                bytes: x.bytes.clone(),
+                format: x.format,
+                tempfile: OnceLock::new(),
+            }),
+        };
+
+        Image {
+            data,
+            preprocess: self.preprocess.clone(),
+        }
+    }
+
+    /// Detects the format from the content and checks that a leptonica built with
+    /// `image_libraries` can read it. The extension of a path is only used to report
+    /// the declared format if the content is not supported.
+    pub(crate) fn check_format(&self, image_libraries: &[String]) -> TessResult<()> {
+        let (detected, declared) = match &self.data {
+            InputData::Path(path) => {
+                let mut header = Vec::new();
+                File::open(path)
+                    .and_then(|file| file.take(32).read_to_end(&mut header))
+                    .map_err(|_| TessError::ImageNotFoundError)?;
+                let declared = path.extension().map(|x| x.to_string_lossy().to_uppercase());
+                (DetectedFormat::detect(&header), declared)
+            }
+            // written as PNG by this crate
+            InputData::Image(_) => return Ok(()),
+            InputData::Bytes(x) => (Some(x.format), None),
+        };
+
+        match detected {
+            Some(format) if format.is_supported(image_libraries) => Ok(()),
+            detected => Err(TessError::ImageFormatError(
+                detected.map(|x| x.to_string()),
+                declared,
+            )),
         }
     }
 
+    /// The image is kept in memory and written to a PNG tempfile when tesseract
+    /// needs a path, after an attached `Preprocess` has been applied.
     pub fn from_dynamic_image(image: &DynamicImage) -> TessResult<Self> {
-        //Store Image as Tempfile
-        let tempfile = tempfile::Builder::new()
-            .prefix("rusty-tesseract")
-            .suffix(".png")
-            .tempfile()
-            .map_err(|e| TessError::TempfileError(e.to_string()))?;
-        let path = tempfile.path();
-        image
-            .save_with_format(path, image::ImageFormat::Png)
-            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+        Ok(Self {
+            data: InputData::Image(DynamicInput {
+                image: Arc::new(image.clone()),
+                tempfile: OnceLock::new(),
+            }),
+            preprocess: None,
+        })
+    }
+
+    /// Encoded image data, e.g. a JPEG from an HTTP body.
+    ///
+    /// The format is detected from the content. The data is piped to tesseract's `stdin`
+    /// without touching the disk, formats tesseract cannot read are converted to a PNG tempfile.
+    pub fn from_bytes<B: Into<Vec<u8>>>(bytes: B) -> TessResult<Self> {
+        let bytes = bytes.into();
+        let format =
+            DetectedFormat::detect(&bytes).ok_or(TessError::ImageFormatError(None, None))?;
+        match format {
+            DetectedFormat::Image(image_format) if !format.is_supported(&[]) => {
+                let image = image::load_from_memory_with_format(&bytes, image_format)
+                    .map_err(|e| TessError::ImageDecodeError(e.to_string()))?;
+                return Self::from_dynamic_image(&image);
+            }
+            _ => {}
+        }
 
         Ok(Self {
-            data: InputData::Image(tempfile),
+            data: InputData::Bytes(InMemoryImage {
+                bytes,
+                format,
+                tempfile: OnceLock::new(),
+            }),
+            preprocess: None,
         })
     }
 
+    /// Reads encoded image data until the end, see `from_bytes`.
+    pub fn from_reader<R: Read>(mut reader: R) -> TessResult<Self> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .map_err(|e| TessError::ImageDecodeError(e.to_string()))?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Returns the data to pipe to tesseract's `stdin` for images created by `from_bytes`.
+    pub(crate) fn stdin_bytes(&self) -> Option<&[u8]> {
+        match &self.data {
+            InputData::Bytes(x) => Some(&x.bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the image of `from_dynamic_image`, which needs no decoding.
+    pub(crate) fn dynamic_image(&self) -> Option<&DynamicImage> {
+        match &self.data {
+            InputData::Image(x) => Some(&x.image),
+            _ => None,
+        }
+    }
+
+    /// Returns the tempfile of an in-memory image, so it can be kept alive elsewhere.
+    pub(crate) fn into_tempfile(self) -> Option<tempfile::NamedTempFile> {
+        match self.data {
+            InputData::Path(_) => None,
+            InputData::Image(x) => x.tempfile.into_inner(),
+            InputData::Bytes(x) => x.tempfile.into_inner(),
+        }
+    }
+
+    /// Images created by `from_dynamic_image` or `from_bytes` are written to a tempfile
+    /// on the first call, e.g. for batch list files that can only refer to paths.
+    /// An attached `Preprocess` is not applied to that file.
     pub fn get_image_path(&self) -> TessResult<&str> {
         match &self.data {
-            InputData::Path(x) => x.to_str(),
-            InputData::Image(x) => x.path().to_str(),
+            InputData::Path(x) => x.as_path(),
+            InputData::Image(x) => x.tempfile()?.path(),
+            InputData::Bytes(x) => x.tempfile()?.path(),
         }
+        .to_str()
         .ok_or(TessError::ImageNotFoundError)
     }
 }
@@ -97,19 +440,80 @@ impl Image {
 #[derive(Debug)]
 enum InputData {
     Path(PathBuf),
-    Image(tempfile::NamedTempFile),
+    Image(DynamicInput),
+    Bytes(InMemoryImage),
+}
+
+#[derive(Debug)]
+struct DynamicInput {
+    image: Arc<DynamicImage>,
+    tempfile: OnceLock<tempfile::NamedTempFile>,
 }
 
+impl DynamicInput {
+    fn tempfile(&self) -> TessResult<&tempfile::NamedTempFile> {
+        if let Some(tempfile) = self.tempfile.get() {
+            return Ok(tempfile);
+        }
+
+        let tempfile = tempfile::Builder::new()
+            .prefix("rusty-tesseract")
+            .suffix(".png")
+            .tempfile()
+            .map_err(|e| TessError::TempfileError(e.to_string()))?;
+        self.image
+            .save_with_format(tempfile.path(), ImageFormat::Png)
+            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+
+        // another thread may have won the race, its tempfile is used instead
+        Ok(self.tempfile.get_or_init(|| tempfile))
+    }
+}
+
+#[derive(Debug)]
+struct InMemoryImage {
+    bytes: Vec<u8>,
+    format: DetectedFormat,
+    tempfile: OnceLock<tempfile::NamedTempFile>,
+}
+
+impl InMemoryImage {
+    fn tempfile(&self) -> TessResult<&tempfile::NamedTempFile> {
+        if let Some(tempfile) = self.tempfile.get() {
+            return Ok(tempfile);
+        }
+
+        let mut tempfile = tempfile::Builder::new()
+            .prefix("rusty-tesseract")
+            .suffix(&format!(".{}", self.format.extension()))
+            .tempfile()
+            .map_err(|e| TessError::TempfileError(e.to_string()))?;
+        tempfile
+            .write_all(&self.bytes)
+            .map_err(|e| TessError::TempfileError(e.to_string()))?;
+
+        // another thread may have won the race, its tempfile is used instead
+        Ok(self.tempfile.get_or_init(|| tempfile))
+    }
+}
+
+/// Shows the path of the image, or the size of in-memory images without writing them to disk.
 impl fmt::Display for Image {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.get_image_path().unwrap())
+        match &self.data {
+            InputData::Path(x) => write!(f, "{}", x.display()),
+            InputData::Image(x) => write!(f, "<{}x{} image>", x.image.width(), x.image.height()),
+            InputData::Bytes(x) => write!(f, "<{} bytes>", x.bytes.len()),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Image;
-    use image::io::Reader as ImageReader;
+    use super::{Args, Image, WordList};
+    use crate::TessError;
+    use image::ImageReader;
+    use std::path::Path;
 
     #[test]
     fn test_from_path() {
@@ -118,6 +522,124 @@ mod tests {
         assert_eq!(input.get_image_path().unwrap(), "img/string.png")
     }
 
+    #[test]
+    fn test_display() {
+        let bytes = std::fs::read("img/string.png").unwrap();
+        let len = bytes.len();
+        let input = Image::from_bytes(bytes).unwrap();
+
+        assert_eq!(input.to_string(), format!("<{} bytes>", len));
+        let dynamic = Image::from_dynamic_image(&image::DynamicImage::new_luma8(3, 2)).unwrap();
+        assert_eq!(dynamic.to_string(), "<3x2 image>");
+        assert_eq!(
+            Image::from_path("img/string.png").unwrap().to_string(),
+            "img/string.png"
+        );
+    }
+
+    #[test]
+    fn test_from_path_detects_format_from_content() {
+        let dir = tempfile::tempdir().unwrap();
+        let extensionless = dir.path().join("upload");
+        let misnamed = dir.path().join("string.jpg");
+        let jpeg2000 = dir.path().join("string.jp2");
+        std::fs::copy("img/string.png", &extensionless).unwrap();
+        std::fs::copy("img/string.png", &misnamed).unwrap();
+        std::fs::write(&jpeg2000, b"\x00\x00\x00\x0cjP  \r\n\x87\n").unwrap();
+
+        assert!(Image::from_path(&extensionless).is_ok());
+        assert!(Image::from_path(&misnamed).is_ok());
+        assert!(Image::from_path(&jpeg2000).is_ok());
+    }
+
+    #[test]
+    fn test_from_path_unsupported_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let text = dir.path().join("text.png");
+        let qoi = dir.path().join("string.qoi");
+        std::fs::write(&text, "not an image").unwrap();
+        ImageReader::open("img/string.png")
+            .unwrap()
+            .decode()
+            .unwrap()
+            .save(&qoi)
+            .unwrap();
+
+        assert_eq!(
+            Image::from_path(&text).unwrap_err(),
+            TessError::ImageFormatError(None, Some("PNG".into()))
+        );
+        assert_eq!(
+            Image::from_path(&qoi).unwrap_err(),
+            TessError::ImageFormatError(Some("QOI".into()), Some("QOI".into()))
+        );
+        assert_eq!(
+            Image::from_path(dir.path().join("missing.png")).unwrap_err(),
+            TessError::ImageNotFoundError
+        );
+    }
+
+    #[test]
+    fn test_from_path_relative_missing_file() {
+        // may exist in the `current_dir` of the engine, the extension is checked instead
+        let img = Image::from_path("img/missing.png").unwrap();
+
+        assert_eq!(img.check_format(&[]), Err(TessError::ImageNotFoundError));
+        assert_eq!(
+            Image::from_path("img/missing.qoi").unwrap_err(),
+            TessError::ImageFormatError(Some("QOI".into()), Some("QOI".into()))
+        );
+        assert_eq!(
+            Image::from_path("img/missing").unwrap_err(),
+            TessError::ImageFormatError(None, None)
+        );
+    }
+
+    #[test]
+    fn test_check_format_with_image_libraries() {
+        let dir = tempfile::tempdir().unwrap();
+        let jpeg2000 = dir.path().join("scan.jp2");
+        std::fs::write(&jpeg2000, b"\x00\x00\x00\x0cjP  \r\n\x87\n").unwrap();
+        let img = Image::from_path(&jpeg2000).unwrap();
+        let png = Image::from_path("img/string.png").unwrap();
+        let libraries = |names: &[&str]| names.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            img.check_format(&libraries(&["libpng", "libtiff"])),
+            Err(TessError::ImageFormatError(
+                Some("JPEG 2000".into()),
+                Some("JP2".into())
+            ))
+        );
+        assert_eq!(
+            img.check_format(&libraries(&["libpng", "libopenjp2"])),
+            Ok(())
+        );
+        assert_eq!(png.check_format(&libraries(&["libpng", "libtiff"])), Ok(()));
+        assert_eq!(
+            png.check_format(&libraries(&["libjpeg"])),
+            Err(TessError::ImageFormatError(
+                Some("PNG".into()),
+                Some("PNG".into())
+            ))
+        );
+    }
+
+    #[test]
+    fn test_resolved() {
+        let img = Image::from_path("string.png").unwrap();
+
+        let resolved = img.resolved(Some(Path::new("img"))).unwrap();
+        assert_eq!(resolved.get_image_path().unwrap(), "img/string.png");
+        assert_eq!(resolved.check_format(&[]), Ok(()));
+        assert!(img.resolved(None).is_none());
+        let absolute = std::fs::canonicalize("img/string.png").unwrap();
+        assert!(Image::from_path(absolute)
+            .unwrap()
+            .resolved(Some(Path::new("img")))
+            .is_none());
+    }
+
     #[test]
     fn test_from_dynamic_image() {
         let img = ImageReader::open("img/string.png")
@@ -133,4 +655,76 @@ mod tests {
 
         assert_eq!(img, tempimg);
     }
+
+    #[test]
+    fn test_from_bytes() {
+        let bytes = std::fs::read("img/string.png").unwrap();
+        let input = Image::from_reader(bytes.as_slice()).unwrap();
+
+        assert_eq!(input.stdin_bytes(), Some(bytes.as_slice()));
+
+        let temppath = input.get_image_path().unwrap();
+        assert!(temppath.ends_with(".png"));
+        assert_eq!(std::fs::read(temppath).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_from_bytes_converts_unsupported_format() {
+        let img = ImageReader::open("img/string.png")
+            .unwrap()
+            .decode()
+            .unwrap();
+        let mut qoi = std::io::Cursor::new(Vec::new());
+        img.write_to(&mut qoi, image::ImageFormat::Qoi).unwrap();
+
+        let input = Image::from_bytes(qoi.into_inner()).unwrap();
+
+        assert_eq!(input.stdin_bytes(), None);
+        assert!(input.get_image_path().unwrap().ends_with(".png"));
+    }
+
+    #[test]
+    fn test_from_bytes_unknown_format() {
+        let result = Image::from_bytes(b"not an image".to_vec());
+
+        assert_eq!(result.unwrap_err(), TessError::ImageFormatError(None, None));
+    }
+
+    #[test]
+    fn test_to_command_args() {
+        let args = Args {
+            config_variables: [
+                ("tessedit_char_whitelist".into(), "ABC".into()),
+                ("load_system_dawg".into(), "0".into()),
+                ("preserve_interword_spaces".into(), "1".into()),
+            ]
+            .into(),
+            user_words: Some(WordList::Entries(vec!["ABC".into()])),
+            config_files: vec!["digits".into()],
+            ..Args::default()
+        };
+
+        assert_eq!(
+            args.to_command_args(),
+            vec![
+                "-l",
+                "eng",
+                "--dpi",
+                "150",
+                "--psm",
+                "3",
+                "--oem",
+                "3",
+                "--user-words",
+                "<in-memory>",
+                "-c",
+                "load_system_dawg=0",
+                "-c",
+                "preserve_interword_spaces=1",
+                "-c",
+                "tessedit_char_whitelist=ABC",
+                "digits",
+            ]
+        );
+    }
 }
@@ -0,0 +1,270 @@
+use super::*;
+use core::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A single tesseract model, either a language (`eng`) or a script model (`script/Latin`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Language {
+    Code(String),
+    Script(String),
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Language::Code(code) => write!(f, "{}", code),
+            Language::Script(script) => write!(f, "script/{}", script),
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = TessError;
+
+    fn from_str(s: &str) -> TessResult<Self> {
+        let invalid = || TessError::ParseError(format!("invalid language '{}'", s));
+        if s.is_empty() || s.contains(|c: char| c == '+' || c.is_whitespace()) {
+            return Err(invalid());
+        }
+
+        match s.strip_prefix("script/") {
+            Some("") => Err(invalid()),
+            Some(script) => Ok(Language::Script(script.into())),
+            None => Ok(Language::Code(s.into())),
+        }
+    }
+}
+
+/// Ordered list of models passed to tesseract via `-l`, the first one being the primary language.
+///
+/// Renders to tesseract's `+`-joined syntax, e.g. `deu+eng+script/Latin`. If the list is empty,
+/// `-l` is omitted and tesseract uses its default language `eng`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Languages(Vec<Language>);
+
+impl Languages {
+    pub fn new(languages: Vec<Language>) -> Self {
+        Languages(languages)
+    }
+
+    /// Appends a language, ignoring duplicates.
+    pub fn push(&mut self, language: Language) {
+        if !self.0.contains(&language) {
+            self.0.push(language);
+        }
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Language> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns all languages that are not contained in `available` (as returned by `get_tesseract_langs`).
+    pub fn unavailable<'a>(
+        &'a self,
+        available: &'a [String],
+    ) -> impl Iterator<Item = &'a Language> {
+        self.0
+            .iter()
+            .filter(move |language| !available.contains(&language.to_string()))
+    }
+
+    /// Checks that every language is installed for the given engine. `tessdata_dir`
+    /// overrides the directory of the engine, like `Args::tessdata_dir`.
+    pub fn check_available(
+        &self,
+        engine: &TesseractEngine,
+        tessdata_dir: Option<&Path>,
+    ) -> TessResult<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        let available = engine.langs_in(tessdata_dir)?;
+        let invalid = self
+            .unavailable(&available)
+            .map(|language| InvalidArgument::UnknownLanguage(language.to_string()))
+            .collect::<Vec<_>>();
+
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(TessError::InvalidArgsError(invalid))
+        }
+    }
+}
+
+impl Default for Languages {
+    fn default() -> Self {
+        Languages(vec![Language::Code("eng".into())])
+    }
+}
+
+impl fmt::Display for Languages {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let languages = self.0.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        write!(f, "{}", languages.join("+"))
+    }
+}
+
+impl FromStr for Languages {
+    type Err = TessError;
+
+    fn from_str(s: &str) -> TessResult<Self> {
+        let languages = s
+            .split('+')
+            .map(Language::from_str)
+            .collect::<TessResult<Vec<_>>>()
+            .map_err(|_| TessError::ParseError(format!("invalid languages '{}'", s)))?;
+        Ok(Languages(languages))
+    }
+}
+
+/// Conversion for struct literals like `lang: "deu+eng".into()`.
+///
+/// Malformed input is passed to tesseract unchanged as a single entry, so that tesseract
+/// or `Args::validate` reports it. Use `parse()` to reject it right away instead.
+impl From<&str> for Languages {
+    fn from(value: &str) -> Self {
+        value
+            .parse()
+            .unwrap_or_else(|_| Languages(vec![Language::Code(value.into())]))
+    }
+}
+
+impl From<String> for Languages {
+    fn from(value: String) -> Self {
+        value.as_str().into()
+    }
+}
+
+impl From<Language> for Languages {
+    fn from(value: Language) -> Self {
+        Languages(vec![value])
+    }
+}
+
+impl From<Vec<Language>> for Languages {
+    fn from(value: Vec<Language>) -> Self {
+        Languages(value)
+    }
+}
+
+impl<'a> IntoIterator for &'a Languages {
+    type Item = &'a Language;
+    type IntoIter = std::slice::Iter<'a, Language>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_languages_round_trip() {
+        let languages: Languages = "deu+eng+script/Latin".parse().unwrap();
+
+        assert_eq!(
+            languages,
+            Languages::new(vec![
+                Language::Code("deu".into()),
+                Language::Code("eng".into()),
+                Language::Script("Latin".into()),
+            ])
+        );
+        assert_eq!(languages.to_string(), "deu+eng+script/Latin");
+        assert_eq!(languages.to_string().parse(), Ok(languages));
+    }
+
+    #[test]
+    fn test_languages_parse_error() {
+        assert_eq!(
+            "deu++eng".parse::<Languages>(),
+            Err(TessError::ParseError("invalid languages 'deu++eng'".into()))
+        );
+        assert!("script/".parse::<Languages>().is_err());
+        assert!("".parse::<Languages>().is_err());
+    }
+
+    #[test]
+    fn test_languages_from_malformed_str() {
+        assert_eq!(Languages::from("deu+eng").iter().count(), 2);
+
+        for malformed in ["deu+", "d e", ""] {
+            let languages = Languages::from(malformed);
+            assert_eq!(languages.to_string(), malformed);
+            assert_eq!(
+                languages.unavailable(&["deu".into()]).collect::<Vec<_>>(),
+                vec![&Language::Code(malformed.into())]
+            );
+        }
+    }
+
+    #[test]
+    fn test_empty_languages() {
+        let args = Args {
+            lang: Languages::new(Vec::new()),
+            ..Args::default()
+        };
+
+        assert!(args.lang.is_empty());
+        assert_eq!(args.to_command_args()[..2], ["--dpi", "150"]);
+        // nothing to check, so tesseract is not run
+        assert_eq!(
+            args.lang
+                .check_available(&TesseractEngine::new("./does-not-exist/tesseract"), None),
+            Ok(())
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_check_available_in_tessdata_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        // lists deu only for the given tessdata directory
+        let executable = dir.path().join("tesseract");
+        std::fs::write(
+            &executable,
+            "#!/bin/sh\necho 'List of available languages (1):'\n\
+            if [ \"$2\" = /custom/tessdata ]; then echo deu; else echo eng; fi\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let engine = TesseractEngine::new(&executable);
+        let languages = Languages::from("deu");
+
+        assert_eq!(
+            languages.check_available(&engine, Some(Path::new("/custom/tessdata"))),
+            Ok(())
+        );
+        assert_eq!(
+            languages.check_available(&engine, None),
+            Err(TessError::InvalidArgsError(vec![
+                InvalidArgument::UnknownLanguage("deu".into())
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_languages_unavailable() {
+        let mut languages = Languages::from("deu+eng");
+        languages.push(Language::Script("Latin".into()));
+        languages.push(Language::Code("eng".into()));
+
+        let available = vec!["eng".to_string(), "script/Latin".to_string()];
+        assert_eq!(
+            languages.unavailable(&available).collect::<Vec<_>>(),
+            vec![&Language::Code("deu".into())]
+        );
+    }
+}
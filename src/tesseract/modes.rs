@@ -0,0 +1,230 @@
+use core::fmt;
+use std::str::FromStr;
+
+use crate::{TessError, TessResult};
+
+/// Page segmentation modes (`--psm`) supported by tesseract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PageSegMode {
+    /// Orientation and script detection (OSD) only.
+    OsdOnly = 0,
+    /// Automatic page segmentation with OSD.
+    AutoOsd = 1,
+    /// Automatic page segmentation, but no OSD, or OCR.
+    AutoOnly = 2,
+    /// Fully automatic page segmentation, but no OSD. (tesseract default)
+    Auto = 3,
+    /// Assume a single column of text of variable sizes.
+    SingleColumn = 4,
+    /// Assume a single uniform block of vertically aligned text.
+    SingleBlockVertText = 5,
+    /// Assume a single uniform block of text.
+    SingleBlock = 6,
+    /// Treat the image as a single text line.
+    SingleLine = 7,
+    /// Treat the image as a single word.
+    SingleWord = 8,
+    /// Treat the image as a single word in a circle.
+    CircleWord = 9,
+    /// Treat the image as a single character.
+    SingleChar = 10,
+    /// Sparse text. Find as much text as possible in no particular order.
+    SparseText = 11,
+    /// Sparse text with OSD.
+    SparseTextOsd = 12,
+    /// Raw line. Treat the image as a single text line, bypassing hacks that are tesseract-specific.
+    RawLine = 13,
+}
+
+impl PageSegMode {
+    const ALL: [PageSegMode; 14] = [
+        PageSegMode::OsdOnly,
+        PageSegMode::AutoOsd,
+        PageSegMode::AutoOnly,
+        PageSegMode::Auto,
+        PageSegMode::SingleColumn,
+        PageSegMode::SingleBlockVertText,
+        PageSegMode::SingleBlock,
+        PageSegMode::SingleLine,
+        PageSegMode::SingleWord,
+        PageSegMode::CircleWord,
+        PageSegMode::SingleChar,
+        PageSegMode::SparseText,
+        PageSegMode::SparseTextOsd,
+        PageSegMode::RawLine,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PageSegMode::OsdOnly => "OsdOnly",
+            PageSegMode::AutoOsd => "AutoOsd",
+            PageSegMode::AutoOnly => "AutoOnly",
+            PageSegMode::Auto => "Auto",
+            PageSegMode::SingleColumn => "SingleColumn",
+            PageSegMode::SingleBlockVertText => "SingleBlockVertText",
+            PageSegMode::SingleBlock => "SingleBlock",
+            PageSegMode::SingleLine => "SingleLine",
+            PageSegMode::SingleWord => "SingleWord",
+            PageSegMode::CircleWord => "CircleWord",
+            PageSegMode::SingleChar => "SingleChar",
+            PageSegMode::SparseText => "SparseText",
+            PageSegMode::SparseTextOsd => "SparseTextOsd",
+            PageSegMode::RawLine => "RawLine",
+        }
+    }
+}
+
+impl fmt::Display for PageSegMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", *self as i32)
+    }
+}
+
+impl TryFrom<i32> for PageSegMode {
+    type Error = TessError;
+
+    fn try_from(value: i32) -> TessResult<Self> {
+        usize::try_from(value)
+            .ok()
+            .and_then(|index| PageSegMode::ALL.get(index).copied())
+            .ok_or(TessError::PageSegModeError(value.to_string()))
+    }
+}
+
+impl From<PageSegMode> for i32 {
+    fn from(value: PageSegMode) -> Self {
+        value as i32
+    }
+}
+
+/// Accepts either the numeric value (`"6"`) or the name of the mode (`"SingleBlock"`).
+impl FromStr for PageSegMode {
+    type Err = TessError;
+
+    fn from_str(s: &str) -> TessResult<Self> {
+        match s.trim().parse::<i32>() {
+            Ok(value) => value.try_into(),
+            Err(_) => PageSegMode::ALL
+                .into_iter()
+                .find(|mode| mode.name().eq_ignore_ascii_case(s.trim()))
+                .ok_or(TessError::PageSegModeError(s.into())),
+        }
+    }
+}
+
+/// OCR engine modes (`--oem`) supported by tesseract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OcrEngineMode {
+    /// Legacy engine only.
+    TesseractOnly = 0,
+    /// Neural nets LSTM engine only.
+    LstmOnly = 1,
+    /// Legacy + LSTM engines.
+    TesseractLstmCombined = 2,
+    /// Default, based on what is available.
+    Default = 3,
+}
+
+impl OcrEngineMode {
+    const ALL: [OcrEngineMode; 4] = [
+        OcrEngineMode::TesseractOnly,
+        OcrEngineMode::LstmOnly,
+        OcrEngineMode::TesseractLstmCombined,
+        OcrEngineMode::Default,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            OcrEngineMode::TesseractOnly => "TesseractOnly",
+            OcrEngineMode::LstmOnly => "LstmOnly",
+            OcrEngineMode::TesseractLstmCombined => "TesseractLstmCombined",
+            OcrEngineMode::Default => "Default",
+        }
+    }
+}
+
+impl fmt::Display for OcrEngineMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", *self as i32)
+    }
+}
+
+impl TryFrom<i32> for OcrEngineMode {
+    type Error = TessError;
+
+    fn try_from(value: i32) -> TessResult<Self> {
+        usize::try_from(value)
+            .ok()
+            .and_then(|index| OcrEngineMode::ALL.get(index).copied())
+            .ok_or(TessError::OcrEngineModeError(value.to_string()))
+    }
+}
+
+impl From<OcrEngineMode> for i32 {
+    fn from(value: OcrEngineMode) -> Self {
+        value as i32
+    }
+}
+
+/// Accepts either the numeric value (`"1"`) or the name of the mode (`"LstmOnly"`).
+impl FromStr for OcrEngineMode {
+    type Err = TessError;
+
+    fn from_str(s: &str) -> TessResult<Self> {
+        match s.trim().parse::<i32>() {
+            Ok(value) => value.try_into(),
+            Err(_) => OcrEngineMode::ALL
+                .into_iter()
+                .find(|mode| mode.name().eq_ignore_ascii_case(s.trim()))
+                .ok_or(TessError::OcrEngineModeError(s.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_page_seg_mode_from_i32() {
+        for value in 0..=13 {
+            let mode = PageSegMode::try_from(value).unwrap();
+            assert_eq!(i32::from(mode), value);
+            assert_eq!(mode.to_string(), value.to_string());
+        }
+
+        assert_eq!(
+            PageSegMode::try_from(14),
+            Err(TessError::PageSegModeError("14".into()))
+        );
+        assert_eq!(
+            PageSegMode::try_from(-1),
+            Err(TessError::PageSegModeError("-1".into()))
+        );
+    }
+
+    #[test]
+    fn test_page_seg_mode_from_str() {
+        assert_eq!("6".parse(), Ok(PageSegMode::SingleBlock));
+        assert_eq!("SparseTextOsd".parse(), Ok(PageSegMode::SparseTextOsd));
+        assert_eq!("rawline".parse(), Ok(PageSegMode::RawLine));
+        assert_eq!(
+            "Paragraph".parse::<PageSegMode>(),
+            Err(TessError::PageSegModeError("Paragraph".into()))
+        );
+    }
+
+    #[test]
+    fn test_ocr_engine_mode_from_str() {
+        assert_eq!("3".parse(), Ok(OcrEngineMode::Default));
+        assert_eq!("LstmOnly".parse(), Ok(OcrEngineMode::LstmOnly));
+        assert_eq!(
+            OcrEngineMode::try_from(2),
+            Ok(OcrEngineMode::TesseractLstmCombined)
+        );
+        assert_eq!(
+            "4".parse::<OcrEngineMode>(),
+            Err(TessError::OcrEngineModeError("4".into()))
+        );
+    }
+}
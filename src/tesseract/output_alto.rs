@@ -0,0 +1,586 @@
+use super::*;
+use core::fmt;
+use std::fmt::Write;
+
+#[derive(Debug, PartialEq)]
+pub struct AltoOutput {
+    pub output: String,
+    pub alto: Alto,
+}
+
+impl fmt::Display for AltoOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+/// ALTO document, see https://www.loc.gov/standards/alto/
+///
+/// Models the layout elements tesseract writes: pages, print spaces, composed blocks,
+/// text blocks, lines and strings. Other elements like illustrations are skipped, and
+/// the text blocks of nested composed blocks belong to the outermost one. `to_xml()` writes
+/// the model back as ALTO v3, so edited results can be passed on to other tools.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Alto {
+    pub description: AltoDescription,
+    pub layout: AltoLayout,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoDescription {
+    pub measurement_unit: String,
+    pub file_name: Option<String>,
+    pub software_name: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoLayout {
+    pub pages: Vec<AltoPage>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoPage {
+    pub id: String,
+    pub width: f32,
+    pub height: f32,
+    pub physical_img_nr: i32,
+    pub print_space: AltoPrintSpace,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoPrintSpace {
+    pub hpos: f32,
+    pub vpos: f32,
+    pub width: f32,
+    pub height: f32,
+    /// text blocks placed directly in the print space, written before the composed blocks
+    pub text_blocks: Vec<AltoTextBlock>,
+    /// tesseract wraps every text block in a composed block
+    pub composed_blocks: Vec<AltoComposedBlock>,
+}
+
+/// `ComposedBlock` element, groups blocks that belong together.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoComposedBlock {
+    pub id: String,
+    pub hpos: f32,
+    pub vpos: f32,
+    pub width: f32,
+    pub height: f32,
+    pub text_blocks: Vec<AltoTextBlock>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoTextBlock {
+    pub id: String,
+    pub hpos: f32,
+    pub vpos: f32,
+    pub width: f32,
+    pub height: f32,
+    pub text_lines: Vec<AltoTextLine>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoTextLine {
+    pub id: String,
+    pub hpos: f32,
+    pub vpos: f32,
+    pub width: f32,
+    pub height: f32,
+    pub strings: Vec<AltoString>,
+}
+
+/// `String` element, i.e. a single word.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AltoString {
+    pub id: String,
+    pub hpos: f32,
+    pub vpos: f32,
+    pub width: f32,
+    pub height: f32,
+    /// word confidence between 0 and 1
+    pub wc: Option<f32>,
+    pub content: String,
+}
+
+impl AltoPrintSpace {
+    /// Text blocks placed directly in the print space, followed by those of the composed blocks.
+    pub fn all_text_blocks(&self) -> impl Iterator<Item = &AltoTextBlock> {
+        self.text_blocks
+            .iter()
+            .chain(self.composed_blocks.iter().flat_map(|x| &x.text_blocks))
+    }
+}
+
+impl AltoComposedBlock {
+    pub fn rect(&self) -> Rect {
+        alto_rect(self.hpos, self.vpos, self.width, self.height)
+    }
+}
+
+impl AltoTextBlock {
+    pub fn rect(&self) -> Rect {
+        alto_rect(self.hpos, self.vpos, self.width, self.height)
+    }
+}
+
+impl AltoTextLine {
+    pub fn rect(&self) -> Rect {
+        alto_rect(self.hpos, self.vpos, self.width, self.height)
+    }
+
+    /// Content of all strings, separated by a space.
+    pub fn text(&self) -> String {
+        self.strings
+            .iter()
+            .map(|x| x.content.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl AltoString {
+    pub fn rect(&self) -> Rect {
+        alto_rect(self.hpos, self.vpos, self.width, self.height)
+    }
+}
+
+/// Tesseract writes whole pixels, other producers may use fractions.
+fn alto_rect(hpos: f32, vpos: f32, width: f32, height: f32) -> Rect {
+    Rect::new(
+        hpos.round() as i32,
+        vpos.round() as i32,
+        width.round() as i32,
+        height.round() as i32,
+    )
+}
+
+impl Alto {
+    pub fn parse(xml: &str) -> TessResult<Self> {
+        string_to_alto(xml)
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::new();
+        self.write_xml(&mut xml)
+            .expect("writing to a String cannot fail");
+        xml
+    }
+
+    fn write_xml(&self, xml: &mut String) -> fmt::Result {
+        let description = &self.description;
+
+        writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            xml,
+            r#"<alto xmlns="http://www.loc.gov/standards/alto/ns-v3#" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.loc.gov/standards/alto/ns-v3# http://www.loc.gov/alto/v3/alto-3-0.xsd">"#
+        )?;
+        writeln!(xml, "\t<Description>")?;
+        writeln!(
+            xml,
+            "\t\t<MeasurementUnit>{}</MeasurementUnit>",
+            escape(&description.measurement_unit)
+        )?;
+        if let Some(file_name) = &description.file_name {
+            writeln!(xml, "\t\t<sourceImageInformation>")?;
+            writeln!(xml, "\t\t\t<fileName>{}</fileName>", escape(file_name))?;
+            writeln!(xml, "\t\t</sourceImageInformation>")?;
+        }
+        if let Some(software_name) = &description.software_name {
+            writeln!(xml, "\t\t<OCRProcessing ID=\"OCR_0\">")?;
+            writeln!(xml, "\t\t\t<ocrProcessingStep>")?;
+            writeln!(xml, "\t\t\t\t<processingSoftware>")?;
+            writeln!(
+                xml,
+                "\t\t\t\t\t<softwareName>{}</softwareName>",
+                escape(software_name)
+            )?;
+            writeln!(xml, "\t\t\t\t</processingSoftware>")?;
+            writeln!(xml, "\t\t\t</ocrProcessingStep>")?;
+            writeln!(xml, "\t\t</OCRProcessing>")?;
+        }
+        writeln!(xml, "\t</Description>")?;
+
+        writeln!(xml, "\t<Layout>")?;
+        for page in &self.layout.pages {
+            let print_space = &page.print_space;
+            writeln!(
+                xml,
+                "\t\t<Page WIDTH=\"{}\" HEIGHT=\"{}\" PHYSICAL_IMG_NR=\"{}\" ID=\"{}\">",
+                page.width,
+                page.height,
+                page.physical_img_nr,
+                escape(&page.id)
+            )?;
+            writeln!(
+                xml,
+                "\t\t\t<PrintSpace HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\">",
+                print_space.hpos, print_space.vpos, print_space.width, print_space.height
+            )?;
+            for block in &print_space.text_blocks {
+                write_text_block(xml, block)?;
+            }
+            for composed in &print_space.composed_blocks {
+                writeln!(
+                    xml,
+                    "\t\t\t\t<ComposedBlock ID=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\">",
+                    escape(&composed.id),
+                    composed.hpos,
+                    composed.vpos,
+                    composed.width,
+                    composed.height
+                )?;
+                for block in &composed.text_blocks {
+                    write_text_block(xml, block)?;
+                }
+                writeln!(xml, "\t\t\t\t</ComposedBlock>")?;
+            }
+            writeln!(xml, "\t\t\t</PrintSpace>")?;
+            writeln!(xml, "\t\t</Page>")?;
+        }
+        writeln!(xml, "\t</Layout>")?;
+        writeln!(xml, "</alto>")
+    }
+}
+
+/// Writes a text block with the indentation of tesseract, which does not indent
+/// the blocks of a composed block any further.
+fn write_text_block(xml: &mut String, block: &AltoTextBlock) -> fmt::Result {
+    writeln!(
+        xml,
+        "\t\t\t\t<TextBlock ID=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\">",
+        escape(&block.id),
+        block.hpos,
+        block.vpos,
+        block.width,
+        block.height
+    )?;
+    for line in &block.text_lines {
+        writeln!(
+            xml,
+            "\t\t\t\t\t<TextLine ID=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\">",
+            escape(&line.id),
+            line.hpos,
+            line.vpos,
+            line.width,
+            line.height
+        )?;
+        write_strings(xml, &line.strings)?;
+        writeln!(xml, "\t\t\t\t\t</TextLine>")?;
+    }
+    writeln!(xml, "\t\t\t\t</TextBlock>")
+}
+
+/// Writes the strings of a line, separated by `SP` elements like tesseract does.
+fn write_strings(xml: &mut String, strings: &[AltoString]) -> fmt::Result {
+    for (index, string) in strings.iter().enumerate() {
+        if index > 0 {
+            let previous = &strings[index - 1];
+            let previous_end = previous.hpos + previous.width;
+            writeln!(
+                xml,
+                "\t\t\t\t\t\t<SP WIDTH=\"{}\" VPOS=\"{}\" HPOS=\"{}\"/>",
+                (string.hpos - previous_end).max(0.0),
+                string.vpos,
+                previous_end
+            )?;
+        }
+
+        write!(
+            xml,
+            "\t\t\t\t\t\t<String ID=\"{}\" HPOS=\"{}\" VPOS=\"{}\" WIDTH=\"{}\" HEIGHT=\"{}\"",
+            escape(&string.id),
+            string.hpos,
+            string.vpos,
+            string.width,
+            string.height
+        )?;
+        if let Some(wc) = string.wc {
+            write!(xml, " WC=\"{}\"", wc)?;
+        }
+        writeln!(xml, " CONTENT=\"{}\"/>", escape(&string.content))?;
+    }
+
+    Ok(())
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl TesseractEngine {
+    pub fn image_to_alto(&self, image: &Image, args: &Args) -> TessResult<AltoOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("alto");
+
+        let output = self.run_tesseract_command(command)?;
+        let alto = string_to_alto(&output)?;
+
+        Ok(AltoOutput { output, alto })
+    }
+}
+
+pub fn image_to_alto(image: &Image, args: &Args) -> TessResult<AltoOutput> {
+    TesseractEngine::default().image_to_alto(image, args)
+}
+
+pub(crate) fn string_to_alto(output: &str) -> TessResult<Alto> {
+    let document = roxmltree::Document::parse(output)
+        .map_err(|e| TessError::ParseError(format!("invalid alto: {}", e)))?;
+    let root = document.root_element();
+
+    Ok(Alto {
+        description: AltoDescription {
+            measurement_unit: find(root, "MeasurementUnit")
+                .and_then(|x| x.text())
+                .unwrap_or("pixel")
+                .into(),
+            file_name: find(root, "fileName")
+                .and_then(|x| x.text())
+                .map(|x| x.into()),
+            software_name: find(root, "softwareName")
+                .and_then(|x| x.text())
+                .map(|x| x.into()),
+        },
+        layout: AltoLayout {
+            pages: elements(root, "Page")
+                .map(|page| {
+                    Ok(AltoPage {
+                        id: attribute(page, "ID"),
+                        width: number(page, "WIDTH")?,
+                        height: number(page, "HEIGHT")?,
+                        physical_img_nr: number(page, "PHYSICAL_IMG_NR")? as i32,
+                        print_space: match find(page, "PrintSpace") {
+                            Some(print_space) => parse_print_space(print_space)?,
+                            None => AltoPrintSpace::default(),
+                        },
+                    })
+                })
+                .collect::<TessResult<_>>()?,
+        },
+    })
+}
+
+fn parse_print_space(print_space: roxmltree::Node) -> TessResult<AltoPrintSpace> {
+    Ok(AltoPrintSpace {
+        hpos: number(print_space, "HPOS")?,
+        vpos: number(print_space, "VPOS")?,
+        width: number(print_space, "WIDTH")?,
+        height: number(print_space, "HEIGHT")?,
+        text_blocks: children(print_space, "TextBlock")
+            .map(parse_text_block)
+            .collect::<TessResult<_>>()?,
+        composed_blocks: children(print_space, "ComposedBlock")
+            .map(|composed| {
+                Ok(AltoComposedBlock {
+                    id: attribute(composed, "ID"),
+                    hpos: number(composed, "HPOS")?,
+                    vpos: number(composed, "VPOS")?,
+                    width: number(composed, "WIDTH")?,
+                    height: number(composed, "HEIGHT")?,
+                    // includes the blocks of nested composed blocks
+                    text_blocks: elements(composed, "TextBlock")
+                        .map(parse_text_block)
+                        .collect::<TessResult<_>>()?,
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+fn parse_text_block(block: roxmltree::Node) -> TessResult<AltoTextBlock> {
+    Ok(AltoTextBlock {
+        id: attribute(block, "ID"),
+        hpos: number(block, "HPOS")?,
+        vpos: number(block, "VPOS")?,
+        width: number(block, "WIDTH")?,
+        height: number(block, "HEIGHT")?,
+        text_lines: elements(block, "TextLine")
+            .map(|line| {
+                Ok(AltoTextLine {
+                    id: attribute(line, "ID"),
+                    hpos: number(line, "HPOS")?,
+                    vpos: number(line, "VPOS")?,
+                    width: number(line, "WIDTH")?,
+                    height: number(line, "HEIGHT")?,
+                    strings: elements(line, "String")
+                        .map(|string| {
+                            Ok(AltoString {
+                                id: attribute(string, "ID"),
+                                hpos: number(string, "HPOS")?,
+                                vpos: number(string, "VPOS")?,
+                                width: number(string, "WIDTH")?,
+                                height: number(string, "HEIGHT")?,
+                                wc: match string.attribute("WC") {
+                                    Some(_) => Some(number(string, "WC")?),
+                                    None => None,
+                                },
+                                content: attribute(string, "CONTENT"),
+                            })
+                        })
+                        .collect::<TessResult<_>>()?,
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+fn elements<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.descendants()
+        .filter(move |x| x.is_element() && x.tag_name().name() == name)
+}
+
+fn children<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.children()
+        .filter(move |x| x.is_element() && x.tag_name().name() == name)
+}
+
+fn find<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &'a str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    elements(node, name).next()
+}
+
+fn attribute(node: roxmltree::Node, name: &str) -> String {
+    node.attribute(name).unwrap_or("").into()
+}
+
+fn number(node: roxmltree::Node, name: &str) -> TessResult<f32> {
+    let value = node.attribute(name).unwrap_or("");
+    value.parse().map_err(|_| {
+        TessError::ParseError(format!(
+            "invalid alto attribute {}=\"{}\" of {}",
+            name,
+            value,
+            node.tag_name().name()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_alto::string_to_alto, *};
+
+    const ALTO: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<alto xmlns="http://www.loc.gov/standards/alto/ns-v3#" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="http://www.loc.gov/standards/alto/ns-v3# http://www.loc.gov/alto/v3/alto-3-0.xsd">
+	<Description>
+		<MeasurementUnit>pixel</MeasurementUnit>
+		<sourceImageInformation>
+			<fileName>img/string.png</fileName>
+		</sourceImageInformation>
+		<OCRProcessing ID="OCR_0">
+			<ocrProcessingStep>
+				<processingSoftware>
+					<softwareName>tesseract 5.3.0</softwareName>
+				</processingSoftware>
+			</ocrProcessingStep>
+		</OCRProcessing>
+	</Description>
+	<Layout>
+		<Page WIDTH="696" HEIGHT="89" PHYSICAL_IMG_NR="0" ID="page_0">
+			<PrintSpace HPOS="0" VPOS="0" WIDTH="696" HEIGHT="89">
+				<ComposedBlock ID="cblock_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+				<TextBlock ID="block_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+					<TextLine ID="line_0" HPOS="18" VPOS="29" WIDTH="653" HEIGHT="35">
+						<String ID="string_0" HPOS="18" VPOS="29" WIDTH="144" HEIGHT="35" WC="0.96" CONTENT="LOREM"/><SP WIDTH="19" VPOS="29" HPOS="162"/>
+						<String ID="string_1" HPOS="181" VPOS="29" WIDTH="123" HEIGHT="35" WC="0.92" CONTENT="IPSUM"/>
+					</TextLine>
+				</TextBlock>
+				</ComposedBlock>
+			</PrintSpace>
+		</Page>
+	</Layout>
+</alto>
+"#;
+
+    #[test]
+    fn test_string_to_alto() {
+        let alto = string_to_alto(ALTO).unwrap();
+
+        assert_eq!(alto.description.file_name, Some("img/string.png".into()));
+        assert_eq!(
+            alto.description.software_name,
+            Some("tesseract 5.3.0".into())
+        );
+
+        let page = &alto.layout.pages[0];
+        assert_eq!((page.width, page.height), (696.0, 89.0));
+
+        assert!(page.print_space.text_blocks.is_empty());
+        let composed = &page.print_space.composed_blocks[0];
+        assert_eq!(composed.id, "cblock_0");
+        assert_eq!(composed.rect(), Rect::new(18, 29, 653, 35));
+
+        let line = &composed.text_blocks[0].text_lines[0];
+        assert_eq!(line.text(), "LOREM IPSUM");
+        assert_eq!(
+            line.strings[1],
+            AltoString {
+                id: "string_1".into(),
+                hpos: 181.0,
+                vpos: 29.0,
+                width: 123.0,
+                height: 35.0,
+                wc: Some(0.92),
+                content: "IPSUM".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_alto_round_trip() {
+        let mut alto = string_to_alto(ALTO).unwrap();
+        let print_space = &mut alto.layout.pages[0].print_space;
+        print_space.composed_blocks[0].text_blocks[0].text_lines[0].strings[0].content =
+            "\"L<O>R&M\"".into();
+        print_space.text_blocks.push(AltoTextBlock {
+            id: "block_1".into(),
+            ..AltoTextBlock::default()
+        });
+
+        let xml = alto.to_xml();
+
+        assert!(xml.contains(r#"<SP WIDTH="19" VPOS="29" HPOS="162"/>"#));
+        assert!(xml.contains(
+            "<ComposedBlock ID=\"cblock_0\" HPOS=\"18\" VPOS=\"29\" WIDTH=\"653\" HEIGHT=\"35\">"
+        ));
+        assert_eq!(Alto::parse(&xml), Ok(alto));
+    }
+
+    #[test]
+    fn test_string_to_alto_parse_error() {
+        let result = string_to_alto(r#"<alto><Layout><Page WIDTH="x"/></Layout></alto>"#);
+        assert_eq!(
+            result,
+            Err(TessError::ParseError(
+                "invalid alto attribute WIDTH=\"x\" of Page".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_image_to_alto() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            psm: Some(PageSegMode::SingleBlock),
+            ..Args::default()
+        };
+
+        let result = image_to_alto(&img, &args).unwrap();
+        let print_space = &result.alto.layout.pages[0].print_space;
+        let line = &print_space.all_text_blocks().next().unwrap().text_lines[0];
+        assert_eq!(line.text(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct BoxOutput {
     pub output: String,
     pub boxes: Vec<Box>,
@@ -13,7 +13,15 @@ impl fmt::Display for BoxOutput {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl BoxOutput {
+    /// Applies `f` to every box and writes the raw `output` anew.
+    pub(crate) fn map_boxes(&mut self, f: impl FnMut(&mut Box)) {
+        self.boxes.iter_mut().for_each(f);
+        self.output = self.boxes.iter().map(|x| format!("{}\n", x)).collect();
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Box {
     pub symbol: String,
     pub left: i32,
@@ -33,6 +41,18 @@ impl fmt::Display for Box {
     }
 }
 
+impl Box {
+    /// Bounding box with the origin at the top left, given the height of the page.
+    pub fn to_rect(&self, image_height: i32) -> Rect {
+        Rect::from_bottom_left(self.left, self.bottom, self.right, self.top, image_height)
+    }
+
+    /// Inverse of `to_rect`, replaces the coordinates with those of `rect`.
+    pub(crate) fn set_rect(&mut self, rect: &Rect, image_height: i32) {
+        (self.left, self.bottom, self.right, self.top) = rect.to_bottom_left(image_height);
+    }
+}
+
 impl FromLine for Box {
     fn from_line(line: &str) -> Option<Self> {
         let mut x = line.split_whitespace();
@@ -48,21 +68,23 @@ impl FromLine for Box {
     }
 }
 
-pub fn image_to_boxes(image: &Image, args: &Args) -> TessResult<BoxOutput> {
-    let mut command = create_tesseract_command(image, args)?;
-    command.arg("makebox");
+impl TesseractEngine {
+    pub fn image_to_boxes(&self, image: &Image, args: &Args) -> TessResult<BoxOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("makebox");
+
+        let output = self.run_tesseract_command(command)?;
+        let boxes = string_to_boxes(&output)?;
+        Ok(BoxOutput { output, boxes })
+    }
+}
 
-    let output = run_tesseract_command(&mut command)?;
-    let boxes = string_to_boxes(&output)?;
-    Ok(BoxOutput { output, boxes })
+pub fn image_to_boxes(image: &Image, args: &Args) -> TessResult<BoxOutput> {
+    TesseractEngine::default().image_to_boxes(image, args)
 }
 
-fn string_to_boxes(output: &str) -> TessResult<Vec<Box>> {
-    output
-        .lines()
-        .into_iter()
-        .map(|line| Box::parse(line.into()))
-        .collect::<_>()
+pub(crate) fn string_to_boxes(output: &str) -> TessResult<Vec<Box>> {
+    output.lines().map(Box::parse).collect::<_>()
 }
 
 #[cfg(test)]
@@ -88,8 +110,10 @@ mod tests {
     #[test]
     fn test_image_to_boxes() {
         let img = Image::from_path("img/string.png").unwrap();
-        let mut image_to_boxes_args = Args::default();
-        image_to_boxes_args.psm = Some(6);
+        let image_to_boxes_args = Args {
+            psm: Some(PageSegMode::SingleBlock),
+            ..Args::default()
+        };
 
         let result = image_to_boxes(&img, &image_to_boxes_args).unwrap();
         assert_eq!(
@@ -0,0 +1,248 @@
+use super::*;
+use std::path::{Path, PathBuf};
+
+/// Output format of a tesseract run, selected via the corresponding config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputKind {
+    Text,
+    Tsv,
+    Boxes,
+    Hocr,
+    Alto,
+    Pdf,
+}
+
+impl OutputKind {
+    pub const ALL: [OutputKind; 6] = [
+        OutputKind::Text,
+        OutputKind::Tsv,
+        OutputKind::Boxes,
+        OutputKind::Hocr,
+        OutputKind::Alto,
+        OutputKind::Pdf,
+    ];
+
+    /// Name of the config passed to tesseract.
+    pub fn config(&self) -> &'static str {
+        match self {
+            OutputKind::Text => "txt",
+            OutputKind::Tsv => "tsv",
+            OutputKind::Boxes => "makebox",
+            OutputKind::Hocr => "hocr",
+            OutputKind::Alto => "alto",
+            OutputKind::Pdf => "pdf",
+        }
+    }
+
+    /// Extension of the file tesseract writes for this config.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputKind::Text => "txt",
+            OutputKind::Tsv => "tsv",
+            OutputKind::Boxes => "box",
+            OutputKind::Hocr => "hocr",
+            OutputKind::Alto => "xml",
+            OutputKind::Pdf => "pdf",
+        }
+    }
+}
+
+/// Results of a single tesseract run with several output formats.
+///
+/// Formats that were not requested are `None`.
+#[derive(Debug, Default, PartialEq)]
+pub struct CombinedOutput {
+    pub text: Option<String>,
+    pub data: Option<DataOutput>,
+    pub boxes: Option<BoxOutput>,
+    pub hocr: Option<HocrOutput>,
+    pub alto: Option<AltoOutput>,
+    pub pdf: Option<PdfOutput>,
+    /// rotation applied by `Args::auto_rotate`, the coordinates of all outputs refer to the rotated image
+    pub rotation: Rotation,
+}
+
+impl TesseractEngine {
+    /// Runs tesseract once and returns all requested outputs,
+    /// instead of running it once per `image_to_*` function.
+    pub fn image_to_outputs(
+        &self,
+        image: &Image,
+        args: &Args,
+        kinds: &[OutputKind],
+    ) -> TessResult<CombinedOutput> {
+        let output_dir = output_tempdir()?;
+        let output_base = output_dir.path().join("output");
+        let command = self.create_outputs_command(image, args, kinds, &output_base)?;
+        let rotation = command.rotation;
+
+        self.run_tesseract_command_bytes(command)?;
+        Ok(CombinedOutput {
+            rotation,
+            ..read_outputs(&output_base, kinds)?
+        })
+    }
+
+    pub(crate) fn create_outputs_command(
+        &self,
+        image: &Image,
+        args: &Args,
+        kinds: &[OutputKind],
+        output_base: &Path,
+    ) -> TessResult<TesseractCommand> {
+        let mut command = self.create_tesseract_command_with_output(image, output_base, args)?;
+        command.args(unique(kinds).map(|x| x.config()));
+
+        Ok(command)
+    }
+}
+
+pub fn image_to_outputs(
+    image: &Image,
+    args: &Args,
+    kinds: &[OutputKind],
+) -> TessResult<CombinedOutput> {
+    TesseractEngine::default().image_to_outputs(image, args, kinds)
+}
+
+pub(crate) fn output_tempdir() -> TessResult<tempfile::TempDir> {
+    tempfile::Builder::new()
+        .prefix("rusty-tesseract")
+        .tempdir()
+        .map_err(|e| TessError::TempfileError(e.to_string()))
+}
+
+/// Reads and parses the files written by tesseract for the given output kinds.
+pub(crate) fn read_outputs(output_base: &Path, kinds: &[OutputKind]) -> TessResult<CombinedOutput> {
+    let mut combined = CombinedOutput::default();
+
+    for kind in unique(kinds) {
+        let path = output_path(output_base, kind);
+        let read_string =
+            || std::fs::read_to_string(&path).map_err(|e| output_file_error(&path, e));
+        match kind {
+            OutputKind::Text => combined.text = Some(read_string()?),
+            OutputKind::Tsv => {
+                let output = read_string()?;
+                let data = string_to_data(&output)?;
+                combined.data = Some(DataOutput { output, data });
+            }
+            OutputKind::Boxes => {
+                let output = read_string()?;
+                let boxes = string_to_boxes(&output)?;
+                combined.boxes = Some(BoxOutput { output, boxes });
+            }
+            OutputKind::Hocr => {
+                let output = read_string()?;
+                let pages = string_to_hocr(&output)?;
+                combined.hocr = Some(HocrOutput { output, pages });
+            }
+            OutputKind::Alto => {
+                let output = read_string()?;
+                let alto = string_to_alto(&output)?;
+                combined.alto = Some(AltoOutput { output, alto });
+            }
+            OutputKind::Pdf => {
+                let output = std::fs::read(&path).map_err(|e| output_file_error(&path, e))?;
+                combined.pdf = Some(PdfOutput { output });
+            }
+        }
+    }
+
+    Ok(combined)
+}
+
+/// Path of the file tesseract writes for `output_base` and the config of `kind`.
+pub(crate) fn output_path(output_base: &Path, kind: OutputKind) -> PathBuf {
+    let mut path = output_base.as_os_str().to_owned();
+    path.push(".");
+    path.push(kind.extension());
+    path.into()
+}
+
+fn output_file_error(path: &Path, error: std::io::Error) -> TessError {
+    TessError::OutputFileError(format!("{}: {}", path.display(), error))
+}
+
+pub(crate) fn unique(kinds: &[OutputKind]) -> impl Iterator<Item = OutputKind> + '_ {
+    kinds
+        .iter()
+        .enumerate()
+        .filter(|(index, kind)| !kinds[..*index].contains(kind))
+        .map(|(_, kind)| *kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_combined::read_outputs, *};
+
+    #[test]
+    fn test_outputs_command() {
+        let engine = TesseractEngine::default();
+        let img = Image::from_path("img/string.png").unwrap();
+        let output_base = std::path::Path::new("out");
+
+        let command = engine
+            .create_outputs_command(
+                &img,
+                &Args::default(),
+                &[OutputKind::Tsv, OutputKind::Text, OutputKind::Tsv],
+                output_base,
+            )
+            .unwrap();
+        let args = command
+            .get_args()
+            .map(|x| x.to_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            args,
+            vec![
+                "img/string.png",
+                "out",
+                "-l",
+                "eng",
+                "--dpi",
+                "150",
+                "--psm",
+                "3",
+                "--oem",
+                "3",
+                "tsv",
+                "txt"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_outputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let output_base = dir.path().join("output");
+        std::fs::write(output_base.with_extension("txt"), "LOREM IPSUM\n").unwrap();
+        std::fs::write(output_base.with_extension("box"), "L 18 26 36 61 0\n").unwrap();
+
+        let result = read_outputs(&output_base, &[OutputKind::Text, OutputKind::Boxes]).unwrap();
+
+        assert_eq!(result.text, Some("LOREM IPSUM\n".into()));
+        assert_eq!(result.boxes.unwrap().boxes[0].symbol, "L");
+        assert_eq!(result.data, None);
+
+        let missing = read_outputs(&output_base, &[OutputKind::Hocr]);
+        assert!(matches!(missing, Err(TessError::OutputFileError(_))));
+    }
+
+    #[test]
+    fn test_image_to_outputs() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let result = image_to_outputs(
+            &img,
+            &Args::default(),
+            &[OutputKind::Text, OutputKind::Tsv, OutputKind::Boxes],
+        )
+        .unwrap();
+
+        assert_eq!(result.text.unwrap().trim(), "LOREM IPSUM DOLOR SIT AMET");
+        assert!(!result.data.unwrap().data.is_empty());
+        assert!(!result.boxes.unwrap().boxes.is_empty());
+    }
+}
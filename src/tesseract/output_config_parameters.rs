@@ -43,25 +43,31 @@ impl FromLine for ConfigParameter {
     }
 }
 
-pub fn get_tesseract_config_parameters() -> TessResult<ConfigParameterOutput> {
-    let mut command = get_tesseract_command();
-    command.arg("--print-parameters");
+impl TesseractEngine {
+    pub fn get_tesseract_config_parameters(&self) -> TessResult<ConfigParameterOutput> {
+        let mut command = self.command();
+        command.arg("--print-parameters");
+
+        let output = self.run_tesseract_command(command)?;
 
-    let output = run_tesseract_command(&mut command)?;
+        let config_parameters = string_to_config_parameter_output(&output)?;
 
-    let config_parameters = string_to_config_parameter_output(&output)?;
+        Ok(ConfigParameterOutput {
+            output,
+            config_parameters,
+        })
+    }
+}
 
-    Ok(ConfigParameterOutput {
-        output,
-        config_parameters,
-    })
+pub fn get_tesseract_config_parameters() -> TessResult<ConfigParameterOutput> {
+    TesseractEngine::default().get_tesseract_config_parameters()
 }
 
-fn string_to_config_parameter_output(output: &str) -> TessResult<Vec<ConfigParameter>> {
+pub(crate) fn string_to_config_parameter_output(output: &str) -> TessResult<Vec<ConfigParameter>> {
     output
         .lines()
         .skip(1)
-        .map(|line| ConfigParameter::parse(line))
+        .map(ConfigParameter::parse)
         .collect::<_>()
 }
 
@@ -1,7 +1,7 @@
 use super::*;
 use core::fmt;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct DataOutput {
     pub output: String,
     pub data: Vec<Data>,
@@ -13,7 +13,32 @@ impl fmt::Display for DataOutput {
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl DataOutput {
+    /// Applies `f` to every row and writes the page number and the bounding box
+    /// back to the raw `output`, the columns are looked up in the header.
+    pub(crate) fn map_data(&mut self, mut f: impl FnMut(&mut Data)) -> TessResult<()> {
+        let mut lines = self.output.lines();
+        let Some(header) = lines.next() else {
+            return Ok(());
+        };
+        let columns = TsvColumns::from_header(header)?;
+
+        let mut mapped = format!("{}\n", header);
+        for (line, data) in lines
+            .filter(|line| !line.trim().is_empty())
+            .zip(&mut self.data)
+        {
+            f(data);
+            mapped.push_str(&columns.write_row(line, data));
+            mapped.push('\n');
+        }
+        self.output = mapped;
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Data {
     pub level: i32,
     pub page_num: i32,
@@ -50,43 +75,132 @@ impl fmt::Display for Data {
     }
 }
 
-impl FromLine for Data {
-    fn from_line(line: &str) -> Option<Self> {
-        let mut x = line.split_whitespace();
+impl Data {
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.left, self.top, self.width, self.height)
+    }
+
+    pub(crate) fn set_rect(&mut self, rect: &Rect) {
+        (self.left, self.top, self.width, self.height) =
+            (rect.left, rect.top, rect.width, rect.height);
+    }
+}
+
+/// Column positions of a tesseract `tsv` output, taken from its header row.
+struct TsvColumns {
+    level: usize,
+    page_num: usize,
+    block_num: usize,
+    par_num: usize,
+    line_num: usize,
+    word_num: usize,
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    conf: usize,
+    text: usize,
+}
+
+impl TsvColumns {
+    fn from_header(header: &str) -> TessResult<Self> {
+        let names = header.split('\t').map(str::trim).collect::<Vec<_>>();
+        let column = |name: &str| {
+            names.iter().position(|&x| x == name).ok_or_else(|| {
+                TessError::ParseError(format!("line 1: missing column '{}' in tsv header", name))
+            })
+        };
+
+        Ok(TsvColumns {
+            level: column("level")?,
+            page_num: column("page_num")?,
+            block_num: column("block_num")?,
+            par_num: column("par_num")?,
+            line_num: column("line_num")?,
+            word_num: column("word_num")?,
+            left: column("left")?,
+            top: column("top")?,
+            width: column("width")?,
+            height: column("height")?,
+            conf: column("conf")?,
+            text: column("text")?,
+        })
+    }
+
+    fn parse_row(&self, row: &str) -> Option<Data> {
+        let cells = row.split('\t').collect::<Vec<_>>();
+        let number = |index: usize| cells.get(index)?.trim().parse().ok();
+
         Some(Data {
-            level: parse_next(&mut x)?,
-            page_num: parse_next(&mut x)?,
-            block_num: parse_next(&mut x)?,
-            par_num: parse_next(&mut x)?,
-            line_num: parse_next(&mut x)?,
-            word_num: parse_next(&mut x)?,
-            left: parse_next(&mut x)?,
-            top: parse_next(&mut x)?,
-            width: parse_next(&mut x)?,
-            height: parse_next(&mut x)?,
-            conf: parse_next(&mut x)?,
-            text: x.next().unwrap_or("").to_string(),
+            level: number(self.level)?,
+            page_num: number(self.page_num)?,
+            block_num: number(self.block_num)?,
+            par_num: number(self.par_num)?,
+            line_num: number(self.line_num)?,
+            word_num: number(self.word_num)?,
+            left: number(self.left)?,
+            top: number(self.top)?,
+            width: number(self.width)?,
+            height: number(self.height)?,
+            conf: cells.get(self.conf)?.trim().parse().ok()?,
+            // rows above word level have no text, some versions also omit the trailing tab
+            text: cells.get(self.text).copied().unwrap_or("").to_string(),
         })
     }
+
+    /// Replaces the page number and bounding box cells of `row` with the values of `data`.
+    fn write_row(&self, row: &str, data: &Data) -> String {
+        let mut cells = row.split('\t').map(String::from).collect::<Vec<_>>();
+        for (index, value) in [
+            (self.page_num, data.page_num),
+            (self.left, data.left),
+            (self.top, data.top),
+            (self.width, data.width),
+            (self.height, data.height),
+        ] {
+            if let Some(cell) = cells.get_mut(index) {
+                *cell = value.to_string();
+            }
+        }
+
+        cells.join("\t")
+    }
 }
 
-pub fn image_to_data(image: &Image, args: &Args) -> TessResult<DataOutput> {
-    let mut command = create_tesseract_command(image, args)?;
-    command.arg("tsv");
+impl TesseractEngine {
+    pub fn image_to_data(&self, image: &Image, args: &Args) -> TessResult<DataOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("tsv");
+
+        let output = self.run_tesseract_command(command)?;
 
-    let output = run_tesseract_command(&mut command)?;
+        let data = string_to_data(&output)?;
 
-    let data = string_to_data(&output)?;
+        Ok(DataOutput { output, data })
+    }
+}
 
-    Ok(DataOutput { output, data })
+pub fn image_to_data(image: &Image, args: &Args) -> TessResult<DataOutput> {
+    TesseractEngine::default().image_to_data(image, args)
 }
 
-fn string_to_data(output: &str) -> TessResult<Vec<Data>> {
-    output
-        .lines()
-        .into_iter()
-        .skip(1)
-        .map(|line| Data::parse(line.into()))
+/// Parses tesseract's `tsv` output. The header row determines the column order,
+/// cells are separated by tabs only, so text containing spaces is kept intact.
+pub(crate) fn string_to_data(output: &str) -> TessResult<Vec<Data>> {
+    let mut lines = output.lines();
+    let columns = match lines.next() {
+        Some(header) => TsvColumns::from_header(header)?,
+        None => return Ok(Vec::new()),
+    };
+
+    lines
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            columns.parse_row(line).ok_or_else(|| {
+                TessError::ParseError(format!("line {}: invalid line '{}'", index + 2, line))
+            })
+        })
         .collect::<_>()
 }
 
@@ -94,10 +208,15 @@ fn string_to_data(output: &str) -> TessResult<Vec<Data>> {
 mod tests {
     use crate::{output_data::string_to_data, *};
 
+    const HEADER: &str =
+        "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext";
+
     #[test]
     fn test_string_to_data() {
-        let result = string_to_data("level   page_num        block_num       par_num line_num        word_num        left    top     width   height  conf    text
-        5       1       1       1       1       1       65      41      46      20      96.063751       The");
+        let result = string_to_data(&format!(
+            "{}\n5\t1\t1\t1\t1\t1\t65\t41\t46\t20\t96.063751\tThe",
+            HEADER
+        ));
         assert_eq!(
             *result.unwrap().first().unwrap(),
             Data {
@@ -111,44 +230,101 @@ mod tests {
                 top: 41,
                 width: 46,
                 height: 20,
-                conf: 96.063751,
+                conf: 96.06375,
                 text: String::from("The"),
             }
         )
     }
 
+    #[test]
+    fn test_string_to_data_text_with_spaces() {
+        let result = string_to_data(&format!(
+            "{}\n\
+            1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+            4\t1\t1\t1\t1\t0\t18\t29\t653\t35\t-1\n\
+            5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t95.643112\tLOREM  IPSUM\n",
+            HEADER
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result.iter().map(|x| x.text.as_str()).collect::<Vec<_>>(),
+            vec!["", "", "LOREM  IPSUM"]
+        );
+    }
+
+    #[test]
+    fn test_string_to_data_reordered_columns() {
+        let result = string_to_data(
+            "text\tconf\tlevel\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\n\
+            AMET\t96.5\t5\t1\t1\t1\t1\t5\t553\t30\t118\t33",
+        )
+        .unwrap();
+
+        assert_eq!(
+            result,
+            vec![Data {
+                level: 5,
+                page_num: 1,
+                block_num: 1,
+                par_num: 1,
+                line_num: 1,
+                word_num: 5,
+                left: 553,
+                top: 30,
+                width: 118,
+                height: 33,
+                conf: 96.5,
+                text: String::from("AMET"),
+            }]
+        );
+    }
+
     #[test]
     fn test_image_to_data() {
         let img = Image::from_path("img/string.png").unwrap();
-        let mut image_to_boxes_args = Args::default();
-        image_to_boxes_args.psm = Some(6);
+        let image_to_boxes_args = Args {
+            psm: Some(PageSegMode::SingleBlock),
+            ..Args::default()
+        };
 
         let result = tesseract::image_to_data(&img, &image_to_boxes_args).unwrap();
         assert_eq!(
             result.data,
-            string_to_data(
-                r#"level   page_num        block_num       par_num line_num        word_num        left    top  width    height  conf    text
-                1       1       0       0       0       0       0       0       696     89      -1
-                2       1       1       0       0       0       18      29      653     35      -1
-                3       1       1       1       0       0       18      29      653     35      -1
-                4       1       1       1       1       0       18      29      653     35      -1
-                5       1       1       1       1       1       18      29      144     35      95.643112    LOREM
-                5       1       1       1       1       2       181     29      123     35      92.306282    IPSUM
-                5       1       1       1       1       3       323     29      153     35      90.531677    DOLOR
-                5       1       1       1       1       4       490     29      50      35      95.873787    SIT
-                5       1       1       1       1       5       553     30      118     33      96.834381    AMET"#
-            )
+            string_to_data(&format!(
+                "{}\n\
+                1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+                2\t1\t1\t0\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                3\t1\t1\t1\t0\t0\t18\t29\t653\t35\t-1\t\n\
+                4\t1\t1\t1\t1\t0\t18\t29\t653\t35\t-1\t\n\
+                5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t95.643112\tLOREM\n\
+                5\t1\t1\t1\t1\t2\t181\t29\t123\t35\t92.306282\tIPSUM\n\
+                5\t1\t1\t1\t1\t3\t323\t29\t153\t35\t90.531677\tDOLOR\n\
+                5\t1\t1\t1\t1\t4\t490\t29\t50\t35\t95.873787\tSIT\n\
+                5\t1\t1\t1\t1\t5\t553\t30\t118\t33\t96.834381\tAMET",
+                HEADER
+            ))
             .unwrap()
         );
     }
 
     #[test]
     fn test_string_to_data_parse_error() {
-        let result = string_to_data("level   page_num        block_num       par_num line_num        word_num        left    top     width   height  conf    text\n\
-        Test");
+        let result = string_to_data(&format!("{}\nTest", HEADER));
+        assert_eq!(
+            result,
+            Err(TessError::ParseError("line 2: invalid line 'Test'".into()))
+        )
+    }
+
+    #[test]
+    fn test_string_to_data_missing_column() {
+        let result = string_to_data("level\tpage_num\ttext\n5\t1\tThe");
         assert_eq!(
             result,
-            Err(TessError::ParseError("invalid line 'Test'".into()))
+            Err(TessError::ParseError(
+                "line 1: missing column 'block_num' in tsv header".into()
+            ))
         )
     }
 }
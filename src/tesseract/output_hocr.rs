@@ -0,0 +1,386 @@
+use super::*;
+use core::fmt;
+use std::collections::HashMap;
+
+#[derive(Debug, PartialEq)]
+pub struct HocrOutput {
+    pub output: String,
+    pub pages: Vec<HocrPage>,
+}
+
+impl fmt::Display for HocrOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+/// `ocr_page` element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HocrPage {
+    pub id: String,
+    pub properties: HocrProperties,
+    pub areas: Vec<HocrArea>,
+}
+
+/// `ocr_carea` element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HocrArea {
+    pub id: String,
+    pub properties: HocrProperties,
+    pub paragraphs: Vec<HocrParagraph>,
+}
+
+/// `ocr_par` element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HocrParagraph {
+    pub id: String,
+    pub lang: Option<String>,
+    pub properties: HocrProperties,
+    pub lines: Vec<HocrLine>,
+}
+
+/// `ocr_line` element, or one of the line-like classes `ocr_header`, `ocr_caption` and `ocr_textfloat`
+#[derive(Clone, Debug, PartialEq)]
+pub struct HocrLine {
+    pub id: String,
+    pub class: String,
+    pub properties: HocrProperties,
+    pub words: Vec<HocrWord>,
+}
+
+/// `ocrx_word` element
+#[derive(Clone, Debug, PartialEq)]
+pub struct HocrWord {
+    pub id: String,
+    pub lang: Option<String>,
+    pub properties: HocrProperties,
+    pub text: String,
+}
+
+/// Bounding box of an hOCR element with the top-left corner as origin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HocrBBox {
+    pub left: i32,
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+}
+
+impl HocrBBox {
+    pub fn rect(&self) -> Rect {
+        Rect::from_corners(self.left, self.top, self.right, self.bottom)
+    }
+}
+
+/// Decoded `title` attribute of an hOCR element.
+///
+/// Properties without a dedicated field are kept unparsed in `other`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HocrProperties {
+    pub bbox: Option<HocrBBox>,
+    /// slope and offset of the baseline
+    pub baseline: Option<(f32, f32)>,
+    pub x_size: Option<f32>,
+    pub x_descenders: Option<f32>,
+    pub x_ascenders: Option<f32>,
+    /// word confidence between 0 and 100
+    pub x_wconf: Option<f32>,
+    pub x_font: Option<String>,
+    pub x_fsize: Option<f32>,
+    pub image: Option<String>,
+    pub ppageno: Option<i32>,
+    pub scan_res: Option<(i32, i32)>,
+    pub textangle: Option<f32>,
+    pub other: HashMap<String, String>,
+}
+
+impl HocrProperties {
+    pub fn parse(title: &str) -> TessResult<Self> {
+        let invalid = || TessError::ParseError(format!("invalid hocr title '{}'", title));
+        let mut properties = HocrProperties::default();
+
+        for property in split_properties(title) {
+            let (name, value) = property.split_once(' ').unwrap_or((property, ""));
+            let value = value.trim();
+            let numbers = || {
+                value
+                    .split_whitespace()
+                    .map(|x| x.parse::<f32>().map_err(|_| invalid()))
+                    .collect::<TessResult<Vec<_>>>()
+            };
+            let number = || match numbers()?.as_slice() {
+                [x] => Ok(*x),
+                _ => Err(invalid()),
+            };
+
+            match name {
+                "bbox" => {
+                    let mut x = value.split_whitespace().map(|x| x.parse::<i32>());
+                    let mut next = || x.next().ok_or_else(invalid)?.map_err(|_| invalid());
+                    properties.bbox = Some(HocrBBox {
+                        left: next()?,
+                        top: next()?,
+                        right: next()?,
+                        bottom: next()?,
+                    });
+                }
+                "baseline" => match numbers()?.as_slice() {
+                    [slope, offset] => properties.baseline = Some((*slope, *offset)),
+                    _ => return Err(invalid()),
+                },
+                "x_size" => properties.x_size = Some(number()?),
+                "x_descenders" => properties.x_descenders = Some(number()?),
+                "x_ascenders" => properties.x_ascenders = Some(number()?),
+                "x_wconf" => properties.x_wconf = Some(number()?),
+                "x_fsize" => properties.x_fsize = Some(number()?),
+                "textangle" => properties.textangle = Some(number()?),
+                "x_font" => properties.x_font = Some(unquote(value).into()),
+                "image" => properties.image = Some(unquote(value).into()),
+                "ppageno" => properties.ppageno = Some(value.parse().map_err(|_| invalid())?),
+                "scan_res" => match numbers()?.as_slice() {
+                    [x, y] => properties.scan_res = Some((*x as i32, *y as i32)),
+                    _ => return Err(invalid()),
+                },
+                _ => {
+                    properties.other.insert(name.into(), value.into());
+                }
+            }
+        }
+
+        Ok(properties)
+    }
+}
+
+impl HocrWord {
+    pub fn conf(&self) -> Option<f32> {
+        self.properties.x_wconf
+    }
+}
+
+impl HocrLine {
+    /// Text of all words, separated by a space.
+    pub fn text(&self) -> String {
+        self.words
+            .iter()
+            .map(|x| x.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl TesseractEngine {
+    pub fn image_to_hocr(&self, image: &Image, args: &Args) -> TessResult<HocrOutput> {
+        let mut command = self.create_tesseract_command(image, args)?;
+        command.arg("hocr");
+
+        let output = self.run_tesseract_command(command)?;
+        let pages = string_to_hocr(&output)?;
+
+        Ok(HocrOutput { output, pages })
+    }
+}
+
+pub fn image_to_hocr(image: &Image, args: &Args) -> TessResult<HocrOutput> {
+    TesseractEngine::default().image_to_hocr(image, args)
+}
+
+const LINE_CLASSES: [&str; 4] = ["ocr_line", "ocr_header", "ocr_caption", "ocr_textfloat"];
+
+pub(crate) fn string_to_hocr(output: &str) -> TessResult<Vec<HocrPage>> {
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let document = roxmltree::Document::parse_with_options(output, options)
+        .map_err(|e| TessError::ParseError(format!("invalid hocr: {}", e)))?;
+
+    elements(document.root(), &["ocr_page"])
+        .map(|page| {
+            Ok(HocrPage {
+                id: attribute(page, "id"),
+                properties: properties(page)?,
+                areas: elements(page, &["ocr_carea"])
+                    .map(|area| {
+                        Ok(HocrArea {
+                            id: attribute(area, "id"),
+                            properties: properties(area)?,
+                            paragraphs: elements(area, &["ocr_par"])
+                                .map(parse_paragraph)
+                                .collect::<TessResult<_>>()?,
+                        })
+                    })
+                    .collect::<TessResult<_>>()?,
+            })
+        })
+        .collect()
+}
+
+fn parse_paragraph(paragraph: roxmltree::Node) -> TessResult<HocrParagraph> {
+    Ok(HocrParagraph {
+        id: attribute(paragraph, "id"),
+        lang: paragraph.attribute("lang").map(|x| x.into()),
+        properties: properties(paragraph)?,
+        lines: elements(paragraph, &LINE_CLASSES)
+            .map(|line| {
+                Ok(HocrLine {
+                    id: attribute(line, "id"),
+                    class: attribute(line, "class"),
+                    properties: properties(line)?,
+                    words: elements(line, &["ocrx_word"])
+                        .map(|word| {
+                            Ok(HocrWord {
+                                id: attribute(word, "id"),
+                                lang: word.attribute("lang").map(|x| x.into()),
+                                properties: properties(word)?,
+                                // words may contain formatting like <strong> or <em>
+                                text: word
+                                    .descendants()
+                                    .filter(|x| x.is_text())
+                                    .filter_map(|x| x.text())
+                                    .collect(),
+                            })
+                        })
+                        .collect::<TessResult<_>>()?,
+                })
+            })
+            .collect::<TessResult<_>>()?,
+    })
+}
+
+/// Descendants of `node` with one of the given classes.
+fn elements<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    classes: &'a [&str],
+) -> impl Iterator<Item = roxmltree::Node<'a, 'input>> {
+    node.descendants().filter(move |x| {
+        x.attribute("class")
+            .is_some_and(|class| class.split_whitespace().any(|c| classes.contains(&c)))
+    })
+}
+
+fn attribute(node: roxmltree::Node, name: &str) -> String {
+    node.attribute(name).unwrap_or("").into()
+}
+
+fn properties(node: roxmltree::Node) -> TessResult<HocrProperties> {
+    HocrProperties::parse(node.attribute("title").unwrap_or(""))
+}
+
+/// Splits a title at `;`, ignoring separators within quoted values.
+fn split_properties(title: &str) -> Vec<&str> {
+    let mut properties = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+
+    for (index, c) in title.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            ';' if !quoted => {
+                properties.push(&title[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    properties.push(&title[start..]);
+
+    properties
+        .into_iter()
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+fn unquote(value: &str) -> &str {
+    value
+        .strip_prefix('"')
+        .and_then(|x| x.strip_suffix('"'))
+        .unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_hocr::string_to_hocr, *};
+
+    const HOCR: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE html PUBLIC "-//W3C//DTD XHTML 1.0 Transitional//EN"
+    "http://www.w3.org/TR/xhtml1/DTD/xhtml1-transitional.dtd">
+<html xmlns="http://www.w3.org/1999/xhtml" xml:lang="en" lang="en">
+ <head>
+  <title></title>
+  <meta http-equiv="Content-Type" content="text/html;charset=utf-8"/>
+  <meta name='ocr-system' content='tesseract 5.3.0' />
+ </head>
+ <body>
+  <div class='ocr_page' id='page_1' title='image "img/a;b.png"; bbox 0 0 696 89; ppageno 0; scan_res 150 150'>
+   <div class='ocr_carea' id='block_1_1' title="bbox 18 29 671 64">
+    <p class='ocr_par' id='par_1_1' lang='eng' title="bbox 18 29 671 64">
+     <span class='ocr_line' id='line_1_1' title="bbox 18 29 671 64; baseline 0.001 -1; x_size 35; x_descenders 0; x_ascenders 9">
+      <span class='ocrx_word' id='word_1_1' title='bbox 18 29 162 64; x_wconf 95'>LOREM</span>
+      <span class='ocrx_word' id='word_1_2' title='bbox 181 29 304 64; x_wconf 92; x_font "Times New Roman"; x_fsize 12'><strong>IPSUM</strong></span>
+     </span>
+    </p>
+   </div>
+  </div>
+ </body>
+</html>
+"#;
+
+    #[test]
+    fn test_string_to_hocr() {
+        let pages = string_to_hocr(HOCR).unwrap();
+
+        let page = &pages[0];
+        assert_eq!(page.id, "page_1");
+        assert_eq!(page.properties.image, Some("img/a;b.png".into()));
+        assert_eq!(page.properties.scan_res, Some((150, 150)));
+        assert_eq!(
+            page.properties.bbox,
+            Some(HocrBBox {
+                left: 0,
+                top: 0,
+                right: 696,
+                bottom: 89
+            })
+        );
+
+        let paragraph = &page.areas[0].paragraphs[0];
+        assert_eq!(paragraph.lang, Some("eng".into()));
+
+        let line = &paragraph.lines[0];
+        assert_eq!(line.class, "ocr_line");
+        assert_eq!(line.properties.baseline, Some((0.001, -1.0)));
+        assert_eq!(line.properties.x_size, Some(35.0));
+        assert_eq!(line.properties.x_ascenders, Some(9.0));
+        assert_eq!(line.text(), "LOREM IPSUM");
+
+        let word = &line.words[1];
+        assert_eq!(word.conf(), Some(92.0));
+        assert_eq!(word.properties.x_font, Some("Times New Roman".into()));
+        assert_eq!(word.properties.x_fsize, Some(12.0));
+    }
+
+    #[test]
+    fn test_string_to_hocr_parse_error() {
+        let result = string_to_hocr("<div class='ocr_page' title='bbox 0 0 x 89'></div>");
+        assert_eq!(
+            result,
+            Err(TessError::ParseError(
+                "invalid hocr title 'bbox 0 0 x 89'".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_image_to_hocr() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let args = Args {
+            psm: Some(PageSegMode::SingleBlock),
+            ..Args::default()
+        };
+
+        let result = image_to_hocr(&img, &args).unwrap();
+        let line = &result.pages[0].areas[0].paragraphs[0].lines[0];
+        assert_eq!(line.text(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+}
@@ -0,0 +1,323 @@
+use super::*;
+use core::fmt;
+use image::GenericImageView;
+use std::io::Cursor;
+
+/// Parsed orientation and script detection (OSD) output of `--psm 0`.
+#[derive(Debug, PartialEq)]
+pub struct Orientation {
+    pub page_number: i32,
+    /// clockwise orientation of the input image
+    pub orientation_degrees: i32,
+    /// clockwise rotation needed to make the page upright
+    pub rotate: i32,
+    pub orientation_confidence: f32,
+    pub script: String,
+    pub script_confidence: f32,
+    pub output: String,
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+/// Clockwise rotation applied to make an image upright, to map coordinates
+/// of the results back to the input image.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rotation {
+    /// one of 0, 90, 180 and 270
+    pub degrees: i32,
+    /// size of the input image, only known if it was rotated
+    original_size: (u32, u32),
+}
+
+impl Rotation {
+    /// Maps a rectangle of the rotated image to the input image, both with the origin at the top left.
+    pub fn rect_to_original(&self, rect: &Rect) -> Rect {
+        let (width, height) = (self.original_size.0 as i32, self.original_size.1 as i32);
+        let (left, top, right, bottom) = (rect.left, rect.top, rect.right(), rect.bottom());
+
+        match self.degrees {
+            90 => Rect::from_corners(top, height - right, bottom, height - left),
+            180 => Rect::from_corners(width - right, height - bottom, width - left, height - top),
+            270 => Rect::from_corners(width - bottom, left, width - top, right),
+            _ => *rect,
+        }
+    }
+
+    pub fn data_to_original(&self, data: &Data) -> Data {
+        let mut data = data.clone();
+        data.set_rect(&self.rect_to_original(&data.rect()));
+        data
+    }
+
+    /// Like `data_to_original`, with the origin of the box coordinates at the bottom left.
+    pub fn box_to_original(&self, x: &Box) -> Box {
+        let (width, height) = self.original_size;
+        let rotated_height = match self.degrees {
+            90 | 270 => width,
+            _ => height,
+        };
+        let rect = self.rect_to_original(&x.to_rect(rotated_height as i32));
+        let mut x = x.clone();
+        x.set_rect(&rect, height as i32);
+        x
+    }
+}
+
+/// Result of `TesseractEngine::image_upright`.
+#[derive(Debug, Default)]
+pub struct Upright {
+    /// the rotated image, `None` if the input is upright already
+    pub image: Option<Image>,
+    pub rotation: Rotation,
+}
+
+impl Orientation {
+    /// Rotates the image upright. The rotated image is kept in memory as PNG,
+    /// images that are upright already are not decoded at all.
+    ///
+    /// Multi-page images are returned unchanged, as decoding keeps only the first page.
+    pub fn rotate_image(&self, image: &Image) -> TessResult<Upright> {
+        let degrees = self.rotate.rem_euclid(360);
+        if !matches!(degrees, 90 | 180 | 270) || image.page_count()? > 1 {
+            return Ok(Upright::default());
+        }
+
+        let decoded = image.decode()?;
+        let rotated = match degrees {
+            90 => decoded.rotate90(),
+            180 => decoded.rotate180(),
+            _ => decoded.rotate270(),
+        };
+        let mut png = Vec::new();
+        rotated
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+
+        Ok(Upright {
+            image: Some(Image::from_bytes(png)?),
+            rotation: Rotation {
+                degrees,
+                original_size: decoded.dimensions(),
+            },
+        })
+    }
+}
+
+impl TesseractEngine {
+    pub fn image_orientation(&self, image: &Image, args: &Args) -> TessResult<Orientation> {
+        let command = self.create_tesseract_command(image, &osd_args(args))?;
+        let output = self.run_tesseract_command(command)?;
+
+        string_to_orientation(output)
+    }
+
+    /// Runs OSD and rotates the image upright, the same step `args.auto_rotate`
+    /// performs before every recognition.
+    ///
+    /// Use the returned `Rotation` to map coordinates of results of the upright
+    /// image back to the input image. Multi-page images are not rotated and OSD is skipped.
+    pub fn image_upright(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        if self.with_resolved(image, Image::page_count)? > 1 {
+            return Ok(Upright::default());
+        }
+
+        let orientation = self.image_orientation(image, args)?;
+        self.with_resolved(image, |x| orientation.rotate_image(x))
+    }
+
+    /// `image_upright` if `args.auto_rotate` is set.
+    pub(crate) fn auto_rotate(&self, image: &Image, args: &Args) -> TessResult<Upright> {
+        if !args.auto_rotate {
+            return Ok(Upright::default());
+        }
+
+        self.image_upright(image, args)
+    }
+}
+
+pub fn image_orientation(image: &Image, args: &Args) -> TessResult<Orientation> {
+    TesseractEngine::default().image_orientation(image, args)
+}
+
+pub fn image_upright(image: &Image, args: &Args) -> TessResult<Upright> {
+    TesseractEngine::default().image_upright(image, args)
+}
+
+pub(crate) fn osd_args(args: &Args) -> Args {
+    Args {
+        psm: Some(PageSegMode::OsdOnly),
+        auto_rotate: false,
+        ..args.clone()
+    }
+}
+
+pub(crate) fn string_to_orientation(output: String) -> TessResult<Orientation> {
+    let value = |key: &str| {
+        output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix(key)?.strip_prefix(':'))
+            .map(str::trim)
+            .ok_or_else(|| TessError::ParseError(format!("missing '{}' in osd output", key)))
+    };
+    let number = |key: &str| {
+        value(key)?
+            .parse::<f32>()
+            .map_err(|_| TessError::ParseError(format!("invalid '{}' in osd output", key)))
+    };
+
+    Ok(Orientation {
+        page_number: number("Page number")? as i32,
+        orientation_degrees: number("Orientation in degrees")? as i32,
+        rotate: number("Rotate")? as i32,
+        orientation_confidence: number("Orientation confidence")?,
+        script: value("Script")?.into(),
+        script_confidence: number("Script confidence")?,
+        output: output.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_orientation::string_to_orientation, *};
+    use image::{GenericImageView, GrayImage, Luma};
+
+    const OSD: &str = "Page number: 0\n\
+        Orientation in degrees: 270\n\
+        Rotate: 90\n\
+        Orientation confidence: 1.42\n\
+        Script: Latin\n\
+        Script confidence: 2.06\n";
+
+    #[test]
+    fn test_string_to_orientation() {
+        let result = string_to_orientation(OSD.into()).unwrap();
+
+        assert_eq!(
+            result,
+            Orientation {
+                page_number: 0,
+                orientation_degrees: 270,
+                rotate: 90,
+                orientation_confidence: 1.42,
+                script: "Latin".into(),
+                script_confidence: 2.06,
+                output: OSD.into(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_string_to_orientation_parse_error() {
+        let result = string_to_orientation("Page number: 0\nRotate: x\n".into());
+
+        assert_eq!(
+            result,
+            Err(TessError::ParseError(
+                "missing 'Orientation in degrees' in osd output".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rotate_image() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let mut orientation = string_to_orientation(OSD.into()).unwrap();
+
+        let upright = orientation.rotate_image(&img).unwrap();
+        let (width, height) = image::open(img.get_image_path().unwrap())
+            .unwrap()
+            .dimensions();
+        let rotated = image::open(upright.image.unwrap().get_image_path().unwrap()).unwrap();
+        assert_eq!(rotated.dimensions(), (height, width));
+        assert_eq!(upright.rotation.degrees, 90);
+
+        orientation.rotate = 0;
+        let upright = orientation.rotate_image(&img).unwrap();
+        assert!(upright.image.is_none());
+        assert_eq!(upright.rotation, Rotation::default());
+    }
+
+    #[test]
+    fn test_multi_page_image_is_not_rotated() {
+        let file = tempfile::Builder::new().suffix(".tiff").tempfile().unwrap();
+        let mut encoder = tiff::encoder::TiffEncoder::new(file.reopen().unwrap()).unwrap();
+        for _ in 0..2 {
+            encoder
+                .write_image::<tiff::encoder::colortype::Gray8>(2, 2, &[0, 255, 255, 0])
+                .unwrap();
+        }
+        let img = Image::from_path(file.path()).unwrap();
+        let orientation = string_to_orientation(OSD.into()).unwrap();
+
+        let upright = orientation.rotate_image(&img).unwrap();
+        assert!(upright.image.is_none());
+        assert_eq!(upright.rotation, Rotation::default());
+
+        // OSD is skipped, the missing executable is never run
+        let engine = TesseractEngine::new("./does-not-exist/tesseract");
+        let args = Args {
+            auto_rotate: true,
+            ..Args::default()
+        };
+        assert!(engine.image_upright(&img, &args).unwrap().image.is_none());
+        let command = engine.create_tesseract_command(&img, &args).unwrap();
+        assert_eq!(command.get_args().next(), Some(file.path().as_os_str()));
+    }
+
+    #[test]
+    fn test_rotation_to_original() {
+        // a 4x2 image with a single marked pixel at x 3, y 0
+        let image = GrayImage::from_fn(4, 2, |x, y| Luma([if (x, y) == (3, 0) { 0 } else { 255 }]));
+        let img = Image::from_dynamic_image(&image.into()).unwrap();
+        let pixel = Rect::new(3, 0, 1, 1);
+
+        for rotate in [90, 180, 270] {
+            let orientation = Orientation {
+                rotate,
+                ..string_to_orientation(OSD.into()).unwrap()
+            };
+            let upright = orientation.rotate_image(&img).unwrap();
+            let rotated = image::load_from_memory(
+                &std::fs::read(upright.image.unwrap().get_image_path().unwrap()).unwrap(),
+            )
+            .unwrap()
+            .to_luma8();
+            let (x, y, _) = rotated.enumerate_pixels().find(|x| x.2 .0[0] == 0).unwrap();
+            let rect = Rect::new(x as i32, y as i32, 1, 1);
+
+            assert_eq!(
+                upright.rotation.rect_to_original(&rect),
+                pixel,
+                "{}",
+                rotate
+            );
+            let (left, bottom, right, top) = rect.to_bottom_left(rotated.height() as i32);
+            let x = Box {
+                symbol: "x".into(),
+                left,
+                bottom,
+                right,
+                top,
+                page: 0,
+            };
+            assert_eq!(
+                upright.rotation.box_to_original(&x).to_rect(2),
+                pixel,
+                "{}",
+                rotate
+            );
+        }
+    }
+
+    #[test]
+    fn test_image_orientation() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let result = image_orientation(&img, &Args::default()).unwrap();
+
+        assert_eq!(result.rotate, 0);
+    }
+}
@@ -0,0 +1,212 @@
+use super::*;
+use std::path::{Path, PathBuf};
+
+/// Searchable PDF written by tesseract's `pdf` config.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PdfOutput {
+    pub output: Vec<u8>,
+}
+
+impl PdfOutput {
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.output
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> TessResult<()> {
+        std::fs::write(path, &self.output).map_err(|e| TessError::OutputFileError(e.to_string()))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PdfMode {
+    /// the image with an invisible text layer on top
+    #[default]
+    ImageAndText,
+    /// only the invisible text layer (`textonly_pdf=1`), to be overlaid on an existing image PDF
+    TextOnly,
+}
+
+impl TesseractEngine {
+    pub fn image_to_pdf(&self, image: &Image, args: &Args, mode: PdfMode) -> TessResult<PdfOutput> {
+        let mut command = self.create_tesseract_command(image, &pdf_args(args, mode))?;
+        command.arg("pdf");
+
+        let output = self.run_tesseract_command_bytes(command)?;
+        Ok(PdfOutput { output })
+    }
+
+    /// Creates a single PDF with one page per image.
+    pub fn images_to_pdf(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: PdfMode,
+    ) -> TessResult<PdfOutput> {
+        let mut command =
+            self.create_tesseract_list_command(images, "stdout", &pdf_args(args, mode))?;
+        command.arg("pdf");
+
+        let output = self.run_tesseract_command_bytes(command)?;
+        Ok(PdfOutput { output })
+    }
+
+    /// Like `image_to_pdf`, but tesseract writes the PDF to `<output_base>.pdf` itself,
+    /// so it is never held in memory. Returns the path of the written file.
+    ///
+    /// Relative paths are resolved against the `current_dir` of the engine.
+    pub fn image_to_pdf_file<P: AsRef<Path>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        mode: PdfMode,
+        output_base: P,
+    ) -> TessResult<PathBuf> {
+        let output_base = &self.resolve_path(output_base.as_ref());
+        let mut command =
+            self.create_tesseract_command_with_output(image, output_base, &pdf_args(args, mode))?;
+        command.arg("pdf");
+
+        self.run_tesseract_command_bytes(command)?;
+        Ok(output_path(output_base, OutputKind::Pdf))
+    }
+
+    /// Like `images_to_pdf`, but writes to `<output_base>.pdf`, see `image_to_pdf_file`.
+    pub fn images_to_pdf_file<P: AsRef<Path>>(
+        &self,
+        images: &[Image],
+        args: &Args,
+        mode: PdfMode,
+        output_base: P,
+    ) -> TessResult<PathBuf> {
+        let output_base = &self.resolve_path(output_base.as_ref());
+        let mut command =
+            self.create_tesseract_list_command(images, output_base, &pdf_args(args, mode))?;
+        command.arg("pdf");
+
+        self.run_tesseract_command_bytes(command)?;
+        Ok(output_path(output_base, OutputKind::Pdf))
+    }
+}
+
+pub fn image_to_pdf(image: &Image, args: &Args, mode: PdfMode) -> TessResult<PdfOutput> {
+    TesseractEngine::default().image_to_pdf(image, args, mode)
+}
+
+pub fn images_to_pdf(images: &[Image], args: &Args, mode: PdfMode) -> TessResult<PdfOutput> {
+    TesseractEngine::default().images_to_pdf(images, args, mode)
+}
+
+pub fn image_to_pdf_file<P: AsRef<Path>>(
+    image: &Image,
+    args: &Args,
+    mode: PdfMode,
+    output_base: P,
+) -> TessResult<PathBuf> {
+    TesseractEngine::default().image_to_pdf_file(image, args, mode, output_base)
+}
+
+pub fn images_to_pdf_file<P: AsRef<Path>>(
+    images: &[Image],
+    args: &Args,
+    mode: PdfMode,
+    output_base: P,
+) -> TessResult<PathBuf> {
+    TesseractEngine::default().images_to_pdf_file(images, args, mode, output_base)
+}
+
+/// `textonly_pdf` has to be passed with `-c`, as it is only read before the `pdf` config.
+pub(crate) fn pdf_args(args: &Args, mode: PdfMode) -> Args {
+    let mut args = args.clone();
+    if mode == PdfMode::TextOnly {
+        args.config_variables
+            .insert("textonly_pdf".into(), "1".into());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{output_pdf::pdf_args, *};
+
+    #[test]
+    fn test_pdf_args() {
+        let args = Args::default();
+
+        assert!(!pdf_args(&args, PdfMode::ImageAndText)
+            .to_command_args()
+            .contains(&"textonly_pdf=1".to_string()));
+        assert!(pdf_args(&args, PdfMode::TextOnly)
+            .to_command_args()
+            .contains(&"textonly_pdf=1".to_string()));
+    }
+
+    #[test]
+    fn test_images_to_pdf_without_images() {
+        let result = images_to_pdf(&[], &Args::default(), PdfMode::ImageAndText);
+
+        assert_eq!(result, Err(TessError::ImageNotFoundError));
+    }
+
+    #[test]
+    fn test_image_to_pdf() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let result = image_to_pdf(&img, &Args::default(), PdfMode::ImageAndText).unwrap();
+
+        assert!(result.as_bytes().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_images_to_pdf() {
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+        let result = images_to_pdf(&images, &Args::default(), PdfMode::TextOnly).unwrap();
+
+        assert!(result.as_bytes().starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_images_to_pdf_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let images = [
+            Image::from_path("img/string.png").unwrap(),
+            Image::from_path("img/string.png").unwrap(),
+        ];
+
+        let path = images_to_pdf_file(
+            &images,
+            &Args::default(),
+            PdfMode::ImageAndText,
+            dir.path().join("scan"),
+        )
+        .unwrap();
+
+        assert_eq!(path, dir.path().join("scan.pdf"));
+        assert!(std::fs::read(path).unwrap().starts_with(b"%PDF"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_image_to_pdf_file_relative_to_current_dir() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        // writes an empty PDF to the output base, like tesseract
+        let executable = dir.path().join("tesseract");
+        std::fs::write(&executable, "#!/bin/sh\nprintf '%%PDF' > \"$2.pdf\"\n").unwrap();
+        std::fs::set_permissions(&executable, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let engine = TesseractEngine {
+            current_dir: Some(dir.path().into()),
+            ..TesseractEngine::new(&executable)
+        };
+        let image = Image::from_path(std::fs::canonicalize("img/string.png").unwrap()).unwrap();
+
+        let path = engine
+            .image_to_pdf_file(&image, &Args::default(), PdfMode::ImageAndText, "scan")
+            .unwrap();
+
+        assert_eq!(path, dir.path().join("scan.pdf"));
+        assert!(std::fs::read(path).unwrap().starts_with(b"%PDF"));
+    }
+}
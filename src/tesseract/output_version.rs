@@ -0,0 +1,309 @@
+use super::*;
+use core::fmt;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// `TesseractVersion::image_libraries` per executable and environment variables, which may
+/// change the linked leptonica, e.g. `LD_LIBRARY_PATH`, so that the version is queried only once.
+static IMAGE_LIBRARIES: OnceLock<Mutex<HashMap<ImageLibrariesKey, Vec<String>>>> = OnceLock::new();
+
+type ImageLibrariesKey = (PathBuf, Vec<(String, String)>);
+
+/// Parsed output of `tesseract --version`.
+///
+/// Versions are compared and ordered by `major`, `minor` and `patch` only,
+/// so two builds of the same release are equal.
+#[derive(Clone, Debug)]
+pub struct TesseractVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// leptonica version, e.g. `1.82.0`
+    pub leptonica: Option<String>,
+    /// image libraries leptonica was built with, e.g. `libpng` or `libopenjp2`;
+    /// they determine which image formats tesseract can read
+    pub image_libraries: Vec<String>,
+    /// features reported as `Found ...`, e.g. `AVX2` or `OpenMP 201511`
+    pub found_features: Vec<String>,
+    pub output: String,
+}
+
+impl fmt::Display for TesseractVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.output)
+    }
+}
+
+impl PartialEq for TesseractVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for TesseractVersion {}
+
+impl PartialOrd for TesseractVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TesseractVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch).cmp(&(other.major, other.minor, other.patch))
+    }
+}
+
+impl TesseractVersion {
+    pub fn at_least(&self, major: u32, minor: u32, patch: u32) -> bool {
+        (self.major, self.minor, self.patch) >= (major, minor, patch)
+    }
+
+    /// Returns `TessError::VersionError` if this version is older than the given one.
+    pub fn require(&self, major: u32, minor: u32, patch: u32) -> TessResult<()> {
+        if self.at_least(major, minor, patch) {
+            Ok(())
+        } else {
+            Err(TessError::VersionError(
+                format!("{}.{}.{}", self.major, self.minor, self.patch),
+                format!("{}.{}.{}", major, minor, patch),
+            ))
+        }
+    }
+}
+
+impl TesseractEngine {
+    pub fn get_tesseract_version(&self) -> TessResult<TesseractVersion> {
+        let mut command = self.command();
+        command.arg("--version");
+
+        let output = self.run_tesseract_command(command)?;
+        string_to_version(output)
+    }
+
+    /// Returns the installed version if it is at least `major.minor.patch`.
+    pub fn require_tesseract_version(
+        &self,
+        major: u32,
+        minor: u32,
+        patch: u32,
+    ) -> TessResult<TesseractVersion> {
+        let version = self.get_tesseract_version()?;
+        version.require(major, minor, patch)?;
+        Ok(version)
+    }
+
+    /// Image libraries of the tesseract build to check image formats against,
+    /// empty if the version cannot be queried.
+    pub(crate) fn image_libraries(&self) -> Vec<String> {
+        cached_image_libraries(self)
+            .unwrap_or_else(|| cache_image_libraries(self, self.get_tesseract_version()))
+    }
+
+    fn image_libraries_key(&self) -> ImageLibrariesKey {
+        let mut envs = self
+            .envs
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<Vec<_>>();
+        envs.sort();
+        (self.executable.clone(), envs)
+    }
+}
+
+pub(crate) fn cached_image_libraries(engine: &TesseractEngine) -> Option<Vec<String>> {
+    let cache = IMAGE_LIBRARIES.get_or_init(Default::default);
+    cache
+        .lock()
+        .unwrap()
+        .get(&engine.image_libraries_key())
+        .cloned()
+}
+
+/// Caches the libraries, or no libraries if the version cannot be queried, e.g. because
+/// the executable does not exist. A timeout or a cancellation is not cached, as the next
+/// query may succeed.
+pub(crate) fn cache_image_libraries(
+    engine: &TesseractEngine,
+    version: TessResult<TesseractVersion>,
+) -> Vec<String> {
+    let libraries = match version {
+        Ok(version) => version.image_libraries,
+        Err(TessError::Timeout(..) | TessError::Cancelled(_)) => return Vec::new(),
+        Err(_) => Vec::new(),
+    };
+    let cache = IMAGE_LIBRARIES.get_or_init(Default::default);
+    cache
+        .lock()
+        .unwrap()
+        .insert(engine.image_libraries_key(), libraries.clone());
+
+    libraries
+}
+
+pub fn get_tesseract_version() -> TessResult<TesseractVersion> {
+    TesseractEngine::default().get_tesseract_version()
+}
+
+pub fn require_tesseract_version(
+    major: u32,
+    minor: u32,
+    patch: u32,
+) -> TessResult<TesseractVersion> {
+    TesseractEngine::default().require_tesseract_version(major, minor, patch)
+}
+
+pub(crate) fn string_to_version(output: String) -> TessResult<TesseractVersion> {
+    let invalid = || TessError::ParseError(format!("invalid version output '{}'", output));
+
+    // e.g. "tesseract 5.3.0", "tesseract 4.1.1-rc2-25-g9707" or "tesseract v5.0.0-alpha.20200328"
+    let version = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("tesseract "))
+        .ok_or_else(invalid)?;
+    let mut numbers = version
+        .trim_start_matches('v')
+        .split(|c: char| !c.is_ascii_digit())
+        .take_while(|x| !x.is_empty())
+        .map(|x| x.parse::<u32>());
+    let major = numbers.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+    let minor = numbers.next().unwrap_or(Ok(0)).map_err(|_| invalid())?;
+    let patch = numbers.next().unwrap_or(Ok(0)).map_err(|_| invalid())?;
+
+    let leptonica = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("leptonica-"))
+        .map(|x| x.into());
+    // e.g. "libgif 5.2.1 : libjpeg 6b (libjpeg-turbo 2.1.2) : libpng 1.6.39 : zlib 1.2.13"
+    let image_libraries = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with("lib") || line.starts_with("zlib"))
+        .flat_map(|line| line.split(" : "))
+        .filter_map(|x| x.split_whitespace().next())
+        .map(|x| x.into())
+        .collect();
+    let found_features = output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Found "))
+        .map(|x| x.into())
+        .collect();
+
+    Ok(TesseractVersion {
+        major,
+        minor,
+        patch,
+        leptonica,
+        image_libraries,
+        found_features,
+        output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        output_version::{cache_image_libraries, cached_image_libraries, string_to_version},
+        *,
+    };
+
+    #[test]
+    fn test_string_to_version() {
+        let result = string_to_version(
+            "tesseract 5.3.0\n \
+            leptonica-1.82.0\n  \
+            libgif 5.2.1 : libjpeg 6b (libjpeg-turbo 2.1.2) : libpng 1.6.39 : libtiff 4.5.0 : zlib 1.2.13\n \
+            Found AVX2\n \
+            Found SSE4.1\n \
+            Found OpenMP 201511\n"
+                .into(),
+        )
+        .unwrap();
+
+        assert_eq!((result.major, result.minor, result.patch), (5, 3, 0));
+        assert_eq!(result.leptonica, Some("1.82.0".into()));
+        assert_eq!(
+            result.image_libraries,
+            vec!["libgif", "libjpeg", "libpng", "libtiff", "zlib"]
+        );
+        assert_eq!(
+            result.found_features,
+            vec!["AVX2", "SSE4.1", "OpenMP 201511"]
+        );
+    }
+
+    #[test]
+    fn test_string_to_version_suffix() {
+        let rc =
+            string_to_version("tesseract 4.1.1-rc2-25-g9707\n leptonica-1.79.0".into()).unwrap();
+        let alpha = string_to_version("tesseract v5.0.0-alpha.20200328".into()).unwrap();
+
+        assert_eq!((rc.major, rc.minor, rc.patch), (4, 1, 1));
+        assert_eq!((alpha.major, alpha.minor, alpha.patch), (5, 0, 0));
+        assert!(rc < alpha);
+        assert!(rc.at_least(4, 0, 0));
+        assert_eq!(
+            rc.require(5, 0, 0),
+            Err(TessError::VersionError("4.1.1".into(), "5.0.0".into()))
+        );
+    }
+
+    #[test]
+    fn test_version_ignores_build_details() {
+        let a =
+            string_to_version("tesseract 5.3.0\n leptonica-1.82.0\n Found AVX2\n".into()).unwrap();
+        let b = string_to_version("tesseract 5.3.0\n leptonica-1.84.1\n".into()).unwrap();
+        let newer = string_to_version("tesseract 5.3.1\n leptonica-1.79.0\n".into()).unwrap();
+
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), std::cmp::Ordering::Equal);
+        assert!(a < newer && b < newer);
+    }
+
+    #[test]
+    fn test_string_to_version_parse_error() {
+        let result = string_to_version("leptonica-1.82.0".into());
+        assert_eq!(
+            result,
+            Err(TessError::ParseError(
+                "invalid version output 'leptonica-1.82.0'".into()
+            ))
+        )
+    }
+
+    #[test]
+    fn test_image_libraries_cached_per_executable_and_envs() {
+        let engine = TesseractEngine::new("./does-not-exist/cached/tesseract");
+        let with_envs = TesseractEngine {
+            envs: [("LD_LIBRARY_PATH".to_string(), "/opt/lib".to_string())].into(),
+            ..engine.clone()
+        };
+
+        // a missing executable is not queried again for every command
+        assert!(engine.image_libraries().is_empty());
+        assert_eq!(cached_image_libraries(&engine), Some(Vec::new()));
+        assert_eq!(cached_image_libraries(&with_envs), None);
+
+        let version = string_to_version("tesseract 5.3.0\n libpng 1.6.39\n".into());
+        assert_eq!(cache_image_libraries(&with_envs, version), vec!["libpng"]);
+        assert_eq!(
+            cached_image_libraries(&with_envs),
+            Some(vec!["libpng".into()])
+        );
+        assert_eq!(cached_image_libraries(&engine), Some(Vec::new()));
+
+        let timeout = TessError::Timeout(std::time::Duration::from_secs(1), String::new());
+        let timed_out = TesseractEngine::new("./does-not-exist/timed-out/tesseract");
+        cache_image_libraries(&timed_out, Err(timeout));
+        assert_eq!(cached_image_libraries(&timed_out), None);
+    }
+
+    #[test]
+    fn test_get_tesseract_version() {
+        let version = get_tesseract_version().unwrap();
+
+        assert!(version.at_least(4, 0, 0));
+    }
+}
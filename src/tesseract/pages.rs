@@ -0,0 +1,401 @@
+use super::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Seek};
+use std::ops::RangeBounds;
+
+/// Result of a single page of a multi-page image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PageResult<T> {
+    /// 0-based page index, like `Box::page`
+    pub page: u32,
+    pub result: T,
+}
+
+impl Image {
+    /// Number of pages, i.e. the number of images in a TIFF file and 1 for all other formats.
+    pub fn page_count(&self) -> TessResult<u32> {
+        if self.dynamic_image().is_some() {
+            return Ok(1);
+        }
+
+        match self.stdin_bytes() {
+            Some(bytes) => count_pages(Cursor::new(bytes)),
+            None => {
+                let file = File::open(self.get_image_path()?)
+                    .map_err(|_| TessError::ImageNotFoundError)?;
+                count_pages(BufReader::new(file))
+            }
+        }
+    }
+}
+
+fn count_pages<R: BufRead + Seek>(reader: R) -> TessResult<u32> {
+    let reader = image::ImageReader::new(reader)
+        .with_guessed_format()
+        .map_err(|e| TessError::ImageDecodeError(e.to_string()))?;
+    if reader.format() != Some(image::ImageFormat::Tiff) {
+        return Ok(1);
+    }
+
+    let mut decoder = tiff::decoder::Decoder::new(reader.into_inner())
+        .map_err(|e| TessError::ImageDecodeError(e.to_string()))?;
+    let mut count = 1;
+    while decoder.more_images() {
+        decoder
+            .next_image()
+            .map_err(|e| TessError::ImageDecodeError(e.to_string()))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+impl TesseractEngine {
+    /// Like `image_to_string`, but returns the text of every page in `pages` separately.
+    ///
+    /// If `pages` selects only some pages, tesseract runs once per selected page with
+    /// `tessedit_page_number`, so the other pages are not recognized at all.
+    pub fn image_to_string_pages<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<String>>> {
+        let page_count = self.with_resolved(image, Image::page_count)?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_string(image, args)?;
+            return string_pages(&output, page_count, ..);
+        };
+
+        selected
+            .into_iter()
+            .map(|page| {
+                let output = self.image_to_string(image, &page_args(args, page))?;
+                single_page_string(&output, page)
+            })
+            .collect()
+    }
+
+    /// Like `image_to_data`, but groups the rows by page, see `image_to_string_pages`.
+    pub fn image_to_data_pages<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<DataOutput>>> {
+        let page_count = self.with_resolved(image, Image::page_count)?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_data(image, args)?;
+            return data_pages(&output, page_count, ..);
+        };
+
+        selected
+            .into_iter()
+            .map(|page| {
+                let output = self.image_to_data(image, &page_args(args, page))?;
+                single_page_data(output, page)
+            })
+            .collect()
+    }
+
+    /// Like `image_to_boxes`, but groups the boxes by page, see `image_to_string_pages`.
+    pub fn image_to_boxes_pages<R: RangeBounds<u32>>(
+        &self,
+        image: &Image,
+        args: &Args,
+        pages: R,
+    ) -> TessResult<Vec<PageResult<BoxOutput>>> {
+        let page_count = self.with_resolved(image, Image::page_count)?;
+        let Some(selected) = page_selection(page_count, &pages) else {
+            let output = self.image_to_boxes(image, args)?;
+            return box_pages(&output, page_count, ..);
+        };
+
+        selected
+            .into_iter()
+            .map(|page| {
+                let output = self.image_to_boxes(image, &page_args(args, page))?;
+                Ok(single_page_boxes(output, page))
+            })
+            .collect()
+    }
+}
+
+pub fn image_to_string_pages<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<String>>> {
+    TesseractEngine::default().image_to_string_pages(image, args, pages)
+}
+
+pub fn image_to_data_pages<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<DataOutput>>> {
+    TesseractEngine::default().image_to_data_pages(image, args, pages)
+}
+
+pub fn image_to_boxes_pages<R: RangeBounds<u32>>(
+    image: &Image,
+    args: &Args,
+    pages: R,
+) -> TessResult<Vec<PageResult<BoxOutput>>> {
+    TesseractEngine::default().image_to_boxes_pages(image, args, pages)
+}
+
+pub(crate) fn string_pages<R: RangeBounds<u32>>(
+    output: &str,
+    page_count: u32,
+    pages: R,
+) -> TessResult<Vec<PageResult<String>>> {
+    let texts = split_pages(output, page_count as usize)?;
+
+    Ok(select_pages(texts, pages))
+}
+
+/// Splits the tsv rows by `page_num`, every page keeps the header row.
+pub(crate) fn data_pages<R: RangeBounds<u32>>(
+    output: &DataOutput,
+    page_count: u32,
+    pages: R,
+) -> TessResult<Vec<PageResult<DataOutput>>> {
+    let mut lines = output.output.lines();
+    let header = lines.next().unwrap_or("");
+    let rows = lines.filter(|line| !line.trim().is_empty());
+
+    let mut split = (0..page_count)
+        .map(|_| DataOutput {
+            output: format!("{}\n", header),
+            data: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    for (line, data) in rows.zip(&output.data) {
+        let page = page_index(data.page_num - 1, page_count)?;
+        split[page].output.push_str(line);
+        split[page].output.push('\n');
+        split[page].data.push(data.clone());
+    }
+
+    Ok(select_pages(split, pages))
+}
+
+pub(crate) fn box_pages<R: RangeBounds<u32>>(
+    output: &BoxOutput,
+    page_count: u32,
+    pages: R,
+) -> TessResult<Vec<PageResult<BoxOutput>>> {
+    let mut split = (0..page_count)
+        .map(|_| BoxOutput {
+            output: String::new(),
+            boxes: Vec::new(),
+        })
+        .collect::<Vec<_>>();
+    for (line, x) in output.output.lines().zip(&output.boxes) {
+        let page = page_index(x.page, page_count)?;
+        split[page].output.push_str(line);
+        split[page].output.push('\n');
+        split[page].boxes.push(x.clone());
+    }
+
+    Ok(select_pages(split, pages))
+}
+
+/// Returns the selected pages, or `None` if all pages are selected and a single run is cheaper.
+pub(crate) fn page_selection<R: RangeBounds<u32>>(page_count: u32, pages: &R) -> Option<Vec<u32>> {
+    let selected = (0..page_count)
+        .filter(|page| pages.contains(page))
+        .collect::<Vec<_>>();
+
+    (selected.len() < page_count as usize).then_some(selected)
+}
+
+/// Restricts the run to a single 0-based page.
+pub(crate) fn page_args(args: &Args, page: u32) -> Args {
+    let mut args = args.clone();
+    args.config_variables
+        .insert("tessedit_page_number".into(), page.to_string());
+    args
+}
+
+pub(crate) fn single_page_string(output: &str, page: u32) -> TessResult<PageResult<String>> {
+    let result = split_pages(output, 1)?.remove(0);
+
+    Ok(PageResult { page, result })
+}
+
+/// tesseract numbers the only page of a `tessedit_page_number` run as the first one.
+pub(crate) fn single_page_data(
+    mut output: DataOutput,
+    page: u32,
+) -> TessResult<PageResult<DataOutput>> {
+    output.map_data(|x| x.page_num = page as i32 + 1)?;
+
+    Ok(PageResult {
+        page,
+        result: output,
+    })
+}
+
+pub(crate) fn single_page_boxes(mut output: BoxOutput, page: u32) -> PageResult<BoxOutput> {
+    output.map_boxes(|x| x.page = page as i32);
+
+    PageResult {
+        page,
+        result: output,
+    }
+}
+
+fn page_index(page: i32, page_count: u32) -> TessResult<usize> {
+    match u32::try_from(page) {
+        Ok(index) if index < page_count => Ok(index as usize),
+        _ => Err(TessError::ParseError(format!(
+            "page {} of an image with {} pages",
+            page, page_count
+        ))),
+    }
+}
+
+fn select_pages<T, R: RangeBounds<u32>>(results: Vec<T>, pages: R) -> Vec<PageResult<T>> {
+    (0..)
+        .zip(results)
+        .filter(|(page, _)| pages.contains(page))
+        .map(|(page, result)| PageResult { page, result })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        output_boxes::string_to_boxes,
+        output_data::string_to_data,
+        pages::{
+            box_pages, data_pages, page_args, page_selection, single_page_boxes, single_page_data,
+            string_pages,
+        },
+        *,
+    };
+
+    fn multi_page_tiff(pages: u32) -> tempfile::NamedTempFile {
+        let file = tempfile::Builder::new().suffix(".tiff").tempfile().unwrap();
+        let mut encoder = tiff::encoder::TiffEncoder::new(file.reopen().unwrap()).unwrap();
+        for _ in 0..pages {
+            encoder
+                .write_image::<tiff::encoder::colortype::Gray8>(2, 2, &[0, 255, 255, 0])
+                .unwrap();
+        }
+        file
+    }
+
+    #[test]
+    fn test_page_count() {
+        let file = multi_page_tiff(3);
+        let tiff = Image::from_path(file.path()).unwrap();
+        let png = Image::from_path("img/string.png").unwrap();
+
+        assert_eq!(tiff.page_count(), Ok(3));
+        let bytes = std::fs::read(file.path()).unwrap();
+        assert_eq!(Image::from_bytes(bytes).unwrap().page_count(), Ok(3));
+        assert_eq!(png.page_count(), Ok(1));
+    }
+
+    #[test]
+    fn test_page_selection() {
+        assert_eq!(page_selection(3, &..), None);
+        assert_eq!(page_selection(3, &(0..5)), None);
+        assert_eq!(page_selection(3, &(1..)), Some(vec![1, 2]));
+        assert_eq!(page_selection(3, &(5..)), Some(vec![]));
+
+        let args = page_args(&Args::default(), 2);
+        assert_eq!(args.config_variables["tessedit_page_number"], "2");
+    }
+
+    #[test]
+    fn test_single_page_results() {
+        let output = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            5\t1\t1\t1\t1\t1\t18\t29\t144\t35\t90\tLOREM\n";
+        let output = DataOutput {
+            output: output.into(),
+            data: string_to_data(output).unwrap(),
+        };
+
+        let result = single_page_data(output, 2).unwrap();
+        assert_eq!(result.page, 2);
+        assert_eq!(result.result.data[0].page_num, 3);
+        assert_eq!(
+            string_to_data(&result.result.output).unwrap(),
+            result.result.data
+        );
+
+        let output = BoxOutput {
+            output: "L 18 26 36 59 0\n".into(),
+            boxes: string_to_boxes("L 18 26 36 59 0\n").unwrap(),
+        };
+        let result = single_page_boxes(output, 2);
+        assert_eq!(result.result.output, "L 18 26 36 59 2\n");
+        assert_eq!(result.result.boxes[0].page, 2);
+    }
+
+    #[test]
+    fn test_string_pages() {
+        let result = string_pages("LOREM\n\x0cIPSUM\n\x0cDOLOR\n\x0c", 3, 1..).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                PageResult {
+                    page: 1,
+                    result: "IPSUM\n".to_string()
+                },
+                PageResult {
+                    page: 2,
+                    result: "DOLOR\n".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_data_pages() {
+        let output = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            1\t1\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+            1\t2\t0\t0\t0\t0\t0\t0\t696\t89\t-1\t\n\
+            5\t2\t1\t1\t1\t1\t18\t29\t144\t35\t90\tLOREM\n";
+        let output = DataOutput {
+            output: output.into(),
+            data: string_to_data(output).unwrap(),
+        };
+
+        let result = data_pages(&output, 2, ..).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].result.data.len(), 1);
+        assert_eq!(result[1].result.data[1].text, "LOREM");
+        assert_eq!(
+            string_to_data(&result[1].result.output).unwrap(),
+            result[1].result.data
+        );
+    }
+
+    #[test]
+    fn test_box_pages() {
+        let output = "L 18 26 36 59 0\nI 40 26 50 59 2\n";
+        let output = BoxOutput {
+            output: output.into(),
+            boxes: string_to_boxes(output).unwrap(),
+        };
+
+        let result = box_pages(&output, 3, ..).unwrap();
+        assert_eq!(result[0].result.output, "L 18 26 36 59 0\n");
+        assert!(result[1].result.boxes.is_empty());
+        assert_eq!(result[2].result.boxes[0].symbol, "I");
+
+        assert_eq!(
+            box_pages(&output, 2, ..),
+            Err(TessError::ParseError(
+                "page 2 of an image with 2 pages".into()
+            ))
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use super::{
+    deskew::{check_max_angle, rotate},
+    *,
+};
+use image::{
+    imageops::{self, FilterType},
+    DynamicImage, GenericImageView, GrayImage, ImageFormat, Luma, Rgba, RgbaImage,
+};
+use std::io::Cursor;
+
+/// Binarization method of `PreprocessStep::Threshold`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Threshold {
+    /// global threshold that best separates the two peaks of the histogram
+    Otsu,
+    /// local threshold from the mean and deviation of a `window` x `window` neighbourhood,
+    /// copes with uneven lighting; `k` is usually between 0.2 and 0.5
+    Sauvola { window: u32, k: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PreprocessStep {
+    Grayscale,
+    /// black text on white, converts the image to grayscale
+    Threshold(Threshold),
+    /// resizes by `target_dpi / source_dpi`, e.g. from the resolution of the capture
+    /// to the 300 DPI tesseract works best with
+    Rescale {
+        source_dpi: u32,
+        target_dpi: u32,
+    },
+    /// median filter against salt-and-pepper noise, converts the image to grayscale
+    Denoise {
+        radius: u32,
+    },
+    /// white border, as tesseract tends to miss text touching the edges
+    Pad {
+        width: u32,
+    },
+    /// inverts images with a dark background, tesseract expects dark text on a light background
+    InvertIfDark,
+    /// straightens text lines skewed by up to `max_angle` degrees,
+    /// see `Image::deskew` to map the results back to the original image
+    Deskew {
+        max_angle: f32,
+    },
+}
+
+/// Steps applied in the given order to an image before OCR, e.g.
+/// `Preprocess::new().rescale(150, 300).grayscale().denoise(1).sauvola(31, 0.3).pad(10)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Preprocess {
+    pub steps: Vec<PreprocessStep>,
+}
+
+impl Preprocess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn step(mut self, step: PreprocessStep) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    pub fn grayscale(self) -> Self {
+        self.step(PreprocessStep::Grayscale)
+    }
+
+    pub fn otsu(self) -> Self {
+        self.step(PreprocessStep::Threshold(Threshold::Otsu))
+    }
+
+    pub fn sauvola(self, window: u32, k: f32) -> Self {
+        self.step(PreprocessStep::Threshold(Threshold::Sauvola { window, k }))
+    }
+
+    pub fn rescale(self, source_dpi: u32, target_dpi: u32) -> Self {
+        self.step(PreprocessStep::Rescale {
+            source_dpi,
+            target_dpi,
+        })
+    }
+
+    pub fn denoise(self, radius: u32) -> Self {
+        self.step(PreprocessStep::Denoise { radius })
+    }
+
+    pub fn pad(self, width: u32) -> Self {
+        self.step(PreprocessStep::Pad { width })
+    }
+
+    pub fn invert_if_dark(self) -> Self {
+        self.step(PreprocessStep::InvertIfDark)
+    }
+
+    pub fn deskew(self, max_angle: f32) -> Self {
+        self.step(PreprocessStep::Deskew { max_angle })
+    }
+
+    pub fn apply(&self, image: &DynamicImage) -> DynamicImage {
+        self.apply_owned(image.clone())
+    }
+
+    fn apply_owned(&self, image: DynamicImage) -> DynamicImage {
+        self.steps
+            .iter()
+            .fold(image, |image, step| step.apply(image))
+    }
+}
+
+impl PreprocessStep {
+    pub fn apply(&self, image: DynamicImage) -> DynamicImage {
+        match *self {
+            PreprocessStep::Grayscale => image.to_luma8().into(),
+            PreprocessStep::Threshold(Threshold::Otsu) => {
+                let mut gray = image.to_luma8();
+                let threshold = otsu_threshold(&gray);
+                for pixel in gray.pixels_mut() {
+                    pixel.0[0] = if pixel.0[0] > threshold { 255 } else { 0 };
+                }
+                gray.into()
+            }
+            PreprocessStep::Threshold(Threshold::Sauvola { window, k }) => {
+                sauvola(&image.to_luma8(), window, k).into()
+            }
+            PreprocessStep::Rescale {
+                source_dpi,
+                target_dpi,
+            } => {
+                if source_dpi == 0 || source_dpi == target_dpi {
+                    return image;
+                }
+                let scale = |x: u32| (x as u64 * target_dpi as u64 / source_dpi as u64).max(1);
+                image.resize_exact(
+                    scale(image.width()) as u32,
+                    scale(image.height()) as u32,
+                    FilterType::CatmullRom,
+                )
+            }
+            PreprocessStep::Denoise { radius } => median(&image.to_luma8(), radius).into(),
+            PreprocessStep::Pad { width } => pad(&image, width),
+            PreprocessStep::InvertIfDark => {
+                let mut image = image;
+                if is_dark(&image) {
+                    image.invert();
+                }
+                image
+            }
+            PreprocessStep::Deskew { max_angle } => {
+                let angle = estimate_skew(&image, max_angle);
+                rotate(&image, angle)
+            }
+        }
+    }
+}
+
+impl Image {
+    /// Attaches steps that are applied when tesseract runs, before the image is piped
+    /// to tesseract, so the image is decoded and encoded only once. The coordinates of
+    /// the results refer to the preprocessed image. Multi-page images cannot be preprocessed.
+    ///
+    /// Fails with `TessError::DeskewAngleError` for an invalid angle of a `Deskew` step.
+    pub fn with_preprocess(mut self, preprocess: Preprocess) -> TessResult<Image> {
+        for step in &preprocess.steps {
+            if let PreprocessStep::Deskew { max_angle } = step {
+                check_max_angle(*max_angle)?;
+            }
+        }
+
+        self.preprocess = Some(preprocess);
+        Ok(self)
+    }
+
+    /// Decodes the image as tesseract gets it, i.e. with the attached `Preprocess` applied.
+    pub(crate) fn decode(&self) -> TessResult<DynamicImage> {
+        let image = match (self.dynamic_image(), self.stdin_bytes()) {
+            (Some(image), _) => image.clone(),
+            (None, Some(bytes)) => image::load_from_memory(bytes)
+                .map_err(|e| TessError::ImageDecodeError(e.to_string()))?,
+            (None, None) => image::open(self.get_image_path()?)
+                .map_err(|e| TessError::ImageDecodeError(e.to_string()))?,
+        };
+
+        Ok(match &self.preprocess {
+            Some(preprocess) => preprocess.apply_owned(image),
+            None => image,
+        })
+    }
+
+    /// Returns the image with the attached `Preprocess` applied as in-memory PNG,
+    /// or `None` if there is nothing to apply.
+    pub(crate) fn preprocessed(&self) -> TessResult<Option<Image>> {
+        if self.preprocess.is_none() {
+            return Ok(None);
+        }
+        if self.page_count()? > 1 {
+            return Err(TessError::ImageDecodeError(
+                "multi-page images cannot be preprocessed".into(),
+            ));
+        }
+
+        let mut png = Vec::new();
+        self.decode()?
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .map_err(|e| TessError::DynamicImageError(e.to_string()))?;
+        Image::from_bytes(png).map(Some)
+    }
+}
+
+/// Returns the gray value that maximizes the variance between the darker and the brighter pixels.
+pub(crate) fn otsu_threshold(gray: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel.0[0] as usize] += 1;
+    }
+    let total = gray.pixels().len() as f64;
+    let sum = (0..256)
+        .map(|x| x as f64 * histogram[x] as f64)
+        .sum::<f64>();
+
+    let (mut weight_dark, mut sum_dark) = (0.0, 0.0);
+    let (mut threshold, mut max_variance) = (0, 0.0);
+    for (value, count) in histogram.iter().enumerate() {
+        weight_dark += *count as f64;
+        sum_dark += value as f64 * *count as f64;
+        let weight_bright = total - weight_dark;
+        if weight_dark == 0.0 || weight_bright == 0.0 {
+            continue;
+        }
+
+        let mean_dark = sum_dark / weight_dark;
+        let mean_bright = (sum - sum_dark) / weight_bright;
+        let variance = weight_dark * weight_bright * (mean_dark - mean_bright).powi(2);
+        if variance > max_variance {
+            threshold = value as u8;
+            max_variance = variance;
+        }
+    }
+
+    threshold
+}
+
+/// Sauvola binarization, the window sums are looked up in integral images.
+fn sauvola(gray: &GrayImage, window: u32, k: f32) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let stride = width as usize + 1;
+    let mut sums = vec![0.0f64; stride * (height as usize + 1)];
+    let mut squares = sums.clone();
+    for (x, y, pixel) in gray.enumerate_pixels() {
+        let value = pixel.0[0] as f64;
+        let (x, y) = (x as usize + 1, y as usize + 1);
+        sums[y * stride + x] = value + sums[(y - 1) * stride + x] + sums[y * stride + x - 1]
+            - sums[(y - 1) * stride + x - 1];
+        squares[y * stride + x] =
+            value * value + squares[(y - 1) * stride + x] + squares[y * stride + x - 1]
+                - squares[(y - 1) * stride + x - 1];
+    }
+
+    let half = window / 2;
+    GrayImage::from_fn(width, height, |x, y| {
+        let (x0, y0) = (
+            x.saturating_sub(half) as usize,
+            y.saturating_sub(half) as usize,
+        );
+        let (x1, y1) = (
+            (x + half + 1).min(width) as usize,
+            (y + half + 1).min(height) as usize,
+        );
+        let area = |table: &[f64]| {
+            table[y1 * stride + x1] - table[y0 * stride + x1] - table[y1 * stride + x0]
+                + table[y0 * stride + x0]
+        };
+        let count = ((x1 - x0) * (y1 - y0)) as f64;
+        let mean = area(&sums) / count;
+        let deviation = (area(&squares) / count - mean * mean).max(0.0).sqrt();
+        // 128 is the dynamic range of the standard deviation of 8 bit images
+        let threshold = mean * (1.0 + k as f64 * (deviation / 128.0 - 1.0));
+
+        Luma([if gray.get_pixel(x, y).0[0] as f64 > threshold {
+            255
+        } else {
+            0
+        }])
+    })
+}
+
+fn median(gray: &GrayImage, radius: u32) -> GrayImage {
+    let (width, height) = gray.dimensions();
+    let mut window = Vec::new();
+
+    GrayImage::from_fn(width, height, |x, y| {
+        window.clear();
+        for ny in y.saturating_sub(radius)..(y + radius + 1).min(height) {
+            for nx in x.saturating_sub(radius)..(x + radius + 1).min(width) {
+                window.push(gray.get_pixel(nx, ny).0[0]);
+            }
+        }
+        window.sort_unstable();
+        Luma([window[window.len() / 2]])
+    })
+}
+
+fn pad(image: &DynamicImage, width: u32) -> DynamicImage {
+    let (w, h) = image.dimensions();
+    let offset = width as i64;
+    match image {
+        DynamicImage::ImageLuma8(gray) => {
+            let mut padded = GrayImage::from_pixel(w + 2 * width, h + 2 * width, Luma([255]));
+            imageops::replace(&mut padded, gray, offset, offset);
+            padded.into()
+        }
+        _ => {
+            let mut padded =
+                RgbaImage::from_pixel(w + 2 * width, h + 2 * width, Rgba([255, 255, 255, 255]));
+            imageops::replace(&mut padded, &image.to_rgba8(), offset, offset);
+            padded.into()
+        }
+    }
+}
+
+fn is_dark(image: &DynamicImage) -> bool {
+    let gray = image.to_luma8();
+    let sum = gray.pixels().map(|x| x.0[0] as u64).sum::<u64>();
+
+    sum < 128 * gray.pixels().len() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        preprocess::{median, otsu_threshold, sauvola},
+        *,
+    };
+    use image::{DynamicImage, GenericImageView, GrayImage, Luma, RgbImage};
+    use std::ffi::OsStr;
+
+    #[test]
+    fn test_otsu() {
+        let gray = GrayImage::from_fn(10, 10, |x, _| {
+            Luma([if x < 3 { 40 + x as u8 } else { 200 }])
+        });
+
+        let threshold = otsu_threshold(&gray);
+        assert!((42..200).contains(&threshold));
+
+        let result = Preprocess::new().otsu().apply(&gray.into()).to_luma8();
+        assert_eq!(result.get_pixel(1, 0), &Luma([0]));
+        assert_eq!(result.get_pixel(5, 0), &Luma([255]));
+    }
+
+    #[test]
+    fn test_sauvola_uneven_lighting() {
+        // background getting brighter from left to right, with a dark stroke every 10 columns
+        let gray = GrayImage::from_fn(60, 20, |x, _| {
+            let background = 90 + 2 * x as u8;
+            Luma([if x % 10 == 5 {
+                background - 60
+            } else {
+                background
+            }])
+        });
+
+        let result = sauvola(&gray, 9, 0.3);
+
+        for x in 0..60 {
+            let expected = if x % 10 == 5 { 0 } else { 255 };
+            assert_eq!(result.get_pixel(x, 10), &Luma([expected]), "column {}", x);
+        }
+    }
+
+    #[test]
+    fn test_denoise() {
+        let mut gray = GrayImage::from_pixel(5, 5, Luma([255]));
+        gray.put_pixel(2, 2, Luma([0]));
+
+        assert_eq!(median(&gray, 1), GrayImage::from_pixel(5, 5, Luma([255])));
+        assert_eq!(median(&gray, 0), gray);
+    }
+
+    #[test]
+    fn test_rescale_pad_and_invert() {
+        let image = DynamicImage::from(RgbImage::from_pixel(20, 10, image::Rgb([0, 0, 0])));
+
+        let result = Preprocess::new()
+            .rescale(100, 300)
+            .invert_if_dark()
+            .grayscale()
+            .pad(5)
+            .apply(&image);
+
+        assert_eq!(result.dimensions(), (70, 40));
+        assert!(result.to_luma8().pixels().all(|x| x.0[0] == 255));
+        assert_eq!(
+            Preprocess::new().invert_if_dark().apply(&result),
+            result,
+            "light images are not inverted"
+        );
+    }
+
+    #[test]
+    fn test_image_with_preprocess() {
+        let img = Image::from_path("img/string.png")
+            .unwrap()
+            .with_preprocess(Preprocess::new().grayscale().pad(10))
+            .unwrap();
+        let (width, height) = image::open("img/string.png").unwrap().dimensions();
+
+        // applied when the command is built, the tesseract run fails without an executable
+        let command = TesseractEngine::new("./does-not-exist/tesseract")
+            .create_tesseract_command(&img, &Args::default())
+            .unwrap();
+
+        assert_eq!(img.get_image_path().unwrap(), "img/string.png");
+        assert_eq!(command.get_args().next(), Some(OsStr::new("stdin")));
+        let preprocessed = image::load_from_memory(command.stdin.as_ref().unwrap()).unwrap();
+        assert_eq!(preprocessed.dimensions(), (width + 20, height + 20));
+        assert!(matches!(preprocessed, DynamicImage::ImageLuma8(_)));
+    }
+
+    #[test]
+    fn test_dynamic_image_with_preprocess_is_not_written() {
+        let img = Image::from_dynamic_image(&image::open("img/string.png").unwrap())
+            .unwrap()
+            .with_preprocess(Preprocess::new().grayscale())
+            .unwrap();
+
+        let command = TesseractEngine::new("./does-not-exist/tesseract")
+            .create_tesseract_command(&img, &Args::default())
+            .unwrap();
+
+        assert!(command.stdin.is_some());
+        assert!(command.tempfiles.is_empty());
+        assert!(img.into_tempfile().is_none());
+    }
+
+    #[test]
+    fn test_with_preprocess_invalid_deskew_angle() {
+        let result = Image::from_path("img/string.png")
+            .unwrap()
+            .with_preprocess(Preprocess::new().deskew(f32::NAN));
+
+        assert_eq!(
+            result.unwrap_err(),
+            TessError::DeskewAngleError("NaN".into())
+        );
+    }
+
+    #[test]
+    fn test_image_to_string_preprocessed() {
+        let img = Image::from_path("img/string.png")
+            .unwrap()
+            .with_preprocess(Preprocess::new().grayscale().otsu().pad(10))
+            .unwrap();
+
+        let result = image_to_string(&img, &Args::default()).unwrap();
+        assert_eq!(result.trim(), "LOREM IPSUM DOLOR SIT AMET");
+    }
+}
@@ -0,0 +1,168 @@
+/// Axis-aligned rectangle with the origin at the top left of the image, like `Data`.
+///
+/// `Box` coordinates start at the bottom left instead, use `Box::to_rect` or
+/// `Rect::from_bottom_left` with the height of the image to convert them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub left: i32,
+    pub top: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn new(left: i32, top: i32, width: i32, height: i32) -> Self {
+        Rect {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// `right` and `bottom` are exclusive.
+    pub fn from_corners(left: i32, top: i32, right: i32, bottom: i32) -> Self {
+        Rect::new(left, top, right - left, bottom - top)
+    }
+
+    /// Converts tesseract's box coordinates with the origin at the bottom left.
+    pub fn from_bottom_left(
+        left: i32,
+        bottom: i32,
+        right: i32,
+        top: i32,
+        image_height: i32,
+    ) -> Self {
+        Rect::from_corners(left, image_height - top, right, image_height - bottom)
+    }
+
+    /// Returns `(left, bottom, right, top)` with the origin at the bottom left, like `Box`.
+    pub fn to_bottom_left(&self, image_height: i32) -> (i32, i32, i32, i32) {
+        (
+            self.left,
+            image_height - self.bottom(),
+            self.right(),
+            image_height - self.top,
+        )
+    }
+
+    /// Moves the rectangle by `dx` to the right and `dy` down.
+    pub fn translate(&self, dx: i32, dy: i32) -> Rect {
+        Rect::new(self.left + dx, self.top + dy, self.width, self.height)
+    }
+
+    pub fn right(&self) -> i32 {
+        self.left + self.width
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.top + self.height
+    }
+
+    pub fn area(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.width as i64 * self.height as i64
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0 || self.height <= 0
+    }
+
+    /// Overlapping area, `None` if the rectangles only touch or are apart.
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect::from_corners(
+            self.left.max(other.left),
+            self.top.max(other.top),
+            self.right().min(other.right()),
+            self.bottom().min(other.bottom()),
+        );
+
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Smallest rectangle containing both.
+    pub fn union(&self, other: &Rect) -> Rect {
+        Rect::from_corners(
+            self.left.min(other.left),
+            self.top.min(other.top),
+            self.right().max(other.right()),
+            self.bottom().max(other.bottom()),
+        )
+    }
+
+    /// Intersection over union, between 0 for disjoint and 1 for equal rectangles.
+    pub fn iou(&self, other: &Rect) -> f32 {
+        let intersection = self.intersection(other).map_or(0, |x| x.area());
+        let union = self.area() + other.area() - intersection;
+        if union == 0 {
+            return 0.0;
+        }
+
+        intersection as f32 / union as f32
+    }
+
+    pub fn contains(&self, other: &Rect) -> bool {
+        other.left >= self.left
+            && other.top >= self.top
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    pub fn contains_point(&self, x: i32, y: i32) -> bool {
+        (self.left..self.right()).contains(&x) && (self.top..self.bottom()).contains(&y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn test_bottom_left_conversion() {
+        let rect = Rect::from_bottom_left(18, 26, 36, 59, 89);
+
+        assert_eq!(rect, Rect::new(18, 30, 18, 33));
+        assert_eq!(rect.to_bottom_left(89), (18, 26, 36, 59));
+    }
+
+    #[test]
+    fn test_translate() {
+        let rect = Rect::new(2, 3, 40, 20).translate(100, 50);
+
+        assert_eq!(rect, Rect::new(102, 53, 40, 20));
+        assert_eq!(rect.translate(-100, -50), Rect::new(2, 3, 40, 20));
+    }
+
+    #[test]
+    fn test_intersection_and_union() {
+        let a = Rect::new(0, 0, 10, 10);
+        let b = Rect::new(5, 5, 10, 10);
+
+        assert_eq!(a.intersection(&b), Some(Rect::new(5, 5, 5, 5)));
+        assert_eq!(a.intersection(&Rect::new(10, 0, 5, 5)), None);
+        assert_eq!(a.union(&b), Rect::new(0, 0, 15, 15));
+    }
+
+    #[test]
+    fn test_iou() {
+        let a = Rect::new(0, 0, 10, 10);
+
+        assert_eq!(a.iou(&a), 1.0);
+        assert_eq!(a.iou(&Rect::new(5, 0, 10, 10)), 50.0 / 150.0);
+        assert_eq!(a.iou(&Rect::new(20, 20, 5, 5)), 0.0);
+        assert_eq!(Rect::default().iou(&Rect::default()), 0.0);
+    }
+
+    #[test]
+    fn test_contains() {
+        let a = Rect::new(0, 0, 10, 10);
+
+        assert!(a.contains(&Rect::new(2, 2, 8, 8)));
+        assert!(!a.contains(&Rect::new(2, 2, 9, 8)));
+        assert!(a.contains_point(0, 9));
+        assert!(!a.contains_point(10, 0));
+    }
+}
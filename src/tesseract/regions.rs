@@ -0,0 +1,256 @@
+use super::*;
+use image::GenericImageView;
+
+/// Rectangle of an image that is recognized on its own, with the origin at the top left like `Data`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Region {
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    /// replaces the `Args` of the whole image, e.g. `psm` 7 and a whitelist for a single form field
+    pub args: Option<Args>,
+}
+
+impl Region {
+    pub fn new(left: u32, top: u32, width: u32, height: u32) -> Self {
+        Region {
+            left,
+            top,
+            width,
+            height,
+            args: None,
+        }
+    }
+
+    pub fn with_args(mut self, args: Args) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    pub fn rect(&self) -> Rect {
+        Rect::new(
+            self.left as i32,
+            self.top as i32,
+            self.width as i32,
+            self.height as i32,
+        )
+    }
+}
+
+/// Outputs of a single region.
+///
+/// `data` and `boxes` are translated to the coordinates of the full image, hOCR, ALTO and PDF
+/// outputs keep the coordinates of the cropped region.
+#[derive(Debug, PartialEq)]
+pub struct RegionOutput {
+    pub region: Region,
+    pub output: CombinedOutput,
+}
+
+impl TesseractEngine {
+    /// Crops every region and runs tesseract once per region with the requested output kinds.
+    ///
+    /// `auto_rotate` is not applied, as rotated regions could not be translated back.
+    pub fn image_to_regions(
+        &self,
+        image: &Image,
+        regions: &[Region],
+        args: &Args,
+        kinds: &[OutputKind],
+    ) -> TessResult<Vec<RegionOutput>> {
+        let (crops, image_height) = self.with_resolved(image, |x| crop_regions(x, regions))?;
+
+        regions
+            .iter()
+            .zip(crops)
+            .map(|(region, crop)| {
+                let output = self.image_to_outputs(&crop, &region_args(region, args), kinds)?;
+                translate_output(region, output, image_height)
+            })
+            .collect()
+    }
+}
+
+pub fn image_to_regions(
+    image: &Image,
+    regions: &[Region],
+    args: &Args,
+    kinds: &[OutputKind],
+) -> TessResult<Vec<RegionOutput>> {
+    TesseractEngine::default().image_to_regions(image, regions, args, kinds)
+}
+
+/// Returns a cropped image per region and the height of the full image.
+pub(crate) fn crop_regions(image: &Image, regions: &[Region]) -> TessResult<(Vec<Image>, u32)> {
+    let image = image.decode()?;
+    let (width, height) = image.dimensions();
+
+    let crops = regions
+        .iter()
+        .map(|region| {
+            let inside = region.width > 0
+                && region.height > 0
+                && region.left as u64 + region.width as u64 <= width as u64
+                && region.top as u64 + region.height as u64 <= height as u64;
+            if !inside {
+                return Err(TessError::RegionError(format!(
+                    "{}x{} at {},{} of a {}x{} image",
+                    region.width, region.height, region.left, region.top, width, height
+                )));
+            }
+
+            let crop = image.crop_imm(region.left, region.top, region.width, region.height);
+            Image::from_dynamic_image(&crop)
+        })
+        .collect::<TessResult<Vec<_>>>()?;
+
+    Ok((crops, height))
+}
+
+pub(crate) fn region_args(region: &Region, args: &Args) -> Args {
+    Args {
+        auto_rotate: false,
+        ..region.args.as_ref().unwrap_or(args).clone()
+    }
+}
+
+/// Moves the data and boxes of a region to the coordinates of the full image,
+/// the raw `output` is rewritten as well.
+pub(crate) fn translate_output(
+    region: &Region,
+    mut output: CombinedOutput,
+    image_height: u32,
+) -> TessResult<RegionOutput> {
+    let offset = region.rect();
+    let to_image = |rect: Rect| rect.translate(offset.left, offset.top);
+
+    if let Some(data) = &mut output.data {
+        data.map_data(|x| x.set_rect(&to_image(x.rect())))?;
+    }
+
+    if let Some(boxes) = &mut output.boxes {
+        // box coordinates start at the bottom of the region and of the image respectively
+        boxes.map_boxes(|x| x.set_rect(&to_image(x.to_rect(offset.height)), image_height as i32));
+    }
+
+    Ok(RegionOutput {
+        region: region.clone(),
+        output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        output_boxes::string_to_boxes,
+        output_data::string_to_data,
+        regions::{crop_regions, translate_output},
+        *,
+    };
+    use image::GenericImageView;
+
+    #[test]
+    fn test_crop_regions() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let (width, height) = image::open("img/string.png").unwrap().dimensions();
+
+        let (crops, image_height) = crop_regions(
+            &img,
+            &[
+                Region::new(10, 20, 100, 30),
+                Region::new(0, 0, width, height),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(image_height, height);
+        let crop = image::open(crops[0].get_image_path().unwrap()).unwrap();
+        assert_eq!(crop.dimensions(), (100, 30));
+
+        assert_eq!(
+            crop_regions(&img, &[Region::new(width - 10, 0, 20, 10)]).unwrap_err(),
+            TessError::RegionError(format!(
+                "20x10 at {},0 of a {}x{} image",
+                width - 10,
+                width,
+                height
+            ))
+        );
+    }
+
+    #[test]
+    fn test_translate_output() {
+        let tsv = "level\tpage_num\tblock_num\tpar_num\tline_num\tword_num\tleft\ttop\twidth\theight\tconf\ttext\n\
+            5\t1\t1\t1\t1\t1\t2\t3\t40\t20\t90\tTOTAL\n";
+        let boxes = "T 2 7 10 27 0\n";
+        let output = CombinedOutput {
+            data: Some(DataOutput {
+                output: tsv.into(),
+                data: string_to_data(tsv).unwrap(),
+            }),
+            boxes: Some(BoxOutput {
+                output: boxes.into(),
+                boxes: string_to_boxes(boxes).unwrap(),
+            }),
+            ..CombinedOutput::default()
+        };
+        let region = Region::new(100, 50, 60, 30);
+
+        let result = translate_output(&region, output, 200).unwrap().output;
+
+        let data = result.data.unwrap();
+        assert_eq!((data.data[0].left, data.data[0].top), (102, 53));
+        assert_eq!(string_to_data(&data.output).unwrap(), data.data);
+        assert!(data.output.ends_with("\t102\t53\t40\t20\t90\tTOTAL\n"));
+        let boxes = result.boxes.unwrap();
+        assert_eq!(boxes.output, "T 102 127 110 147 0\n");
+        assert_eq!(string_to_boxes(&boxes.output).unwrap(), boxes.boxes);
+    }
+
+    #[test]
+    fn test_translate_output_column_order() {
+        let tsv = "text\tlevel\tpage_num\tblock_num\tpar_num\tline_num\tword_num\ttop\tleft\twidth\theight\tconf\n\
+            TOTAL\t5\t1\t1\t1\t1\t1\t3\t2\t40\t20\t90\n";
+        let output = CombinedOutput {
+            data: Some(DataOutput {
+                output: tsv.into(),
+                data: string_to_data(tsv).unwrap(),
+            }),
+            ..CombinedOutput::default()
+        };
+
+        let result = translate_output(&Region::new(100, 50, 60, 30), output, 200).unwrap();
+
+        let data = result.output.data.unwrap();
+        assert_eq!(
+            data.output.lines().nth(1),
+            Some("TOTAL\t5\t1\t1\t1\t1\t1\t53\t102\t40\t20\t90")
+        );
+        assert_eq!(string_to_data(&data.output).unwrap(), data.data);
+    }
+
+    #[test]
+    fn test_image_to_regions() {
+        let img = Image::from_path("img/string.png").unwrap();
+        let (_, height) = image::open("img/string.png").unwrap().dimensions();
+        let word = Args {
+            psm: Some(PageSegMode::SingleWord),
+            ..Args::default()
+        };
+        let regions = [Region::new(0, 0, 180, height).with_args(word)];
+
+        let result = image_to_regions(
+            &img,
+            &regions,
+            &Args::default(),
+            &[OutputKind::Text, OutputKind::Tsv],
+        )
+        .unwrap();
+
+        assert_eq!(result[0].output.text.as_ref().unwrap().trim(), "LOREM");
+        let data = result[0].output.data.as_ref().unwrap();
+        let lorem = data.data.iter().find(|x| x.text == "LOREM").unwrap();
+        assert!((10..30).contains(&lorem.left));
+    }
+}